@@ -1,12 +1,17 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
-use walkdir::WalkDir;
+use tauri::State;
 use xxhash_rust::xxh3::xxh3_64;
 
 use crate::commands::scanner::FileInfo;
+use crate::commands::quarantine::{self, Removal};
+use crate::database::{self, DbPath};
 use crate::services::classifier::classify_extension;
+use crate::services::hasher::files_identical;
+use crate::services::jobs::JobControl;
+use crate::services::link_policy::{link_target, SymlinkPolicy, WalkPolicy};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -50,8 +55,9 @@ pub struct FolderTreeNode {
 
 /// Analyze a folder and return statistics
 #[tauri::command]
-pub fn analyze_folder(path: String) -> Result<FolderStats, String> {
+pub fn analyze_folder(path: String, db_path: State<DbPath>) -> Result<FolderStats, String> {
     let folder_path = PathBuf::from(&path);
+    let walk_policy = WalkPolicy::load(&db_path.0);
 
     if !folder_path.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
@@ -64,19 +70,21 @@ pub fn analyze_folder(path: String) -> Result<FolderStats, String> {
     let mut largest_size: u64 = 0;
     let mut category_breakdown: HashMap<String, CategoryStats> = HashMap::new();
 
-    for entry in WalkDir::new(&folder_path)
+    for entry in walk_policy
+        .walk_dir(&folder_path)
         .min_depth(1)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let entry_path = entry.path();
 
-        if entry_path.is_dir() {
+        if entry.file_type().is_dir() {
             folder_count += 1;
             continue;
         }
 
-        if let Ok(metadata) = fs::metadata(entry_path) {
+        if let Ok(metadata) = entry.metadata() {
             let size = metadata.len();
             total_size += size;
             file_count += 1;
@@ -92,6 +100,7 @@ pub fn analyze_folder(path: String) -> Result<FolderStats, String> {
 
                 let category = classify_extension(&extension);
 
+                let link_target = link_target(entry_path);
                 largest_file = Some(FileInfo {
                     path: entry_path.to_string_lossy().to_string(),
                     name: entry_path.file_name()
@@ -109,6 +118,8 @@ pub fn analyze_folder(path: String) -> Result<FolderStats, String> {
                         .map(|n| n.starts_with('.'))
                         .unwrap_or(false),
                     category,
+                    is_symlink: link_target.is_some(),
+                    link_target,
                 });
             }
 
@@ -150,8 +161,13 @@ pub fn analyze_folder(path: String) -> Result<FolderStats, String> {
 
 /// Find duplicate files in a folder using xxHash
 #[tauri::command]
-pub fn find_duplicates(path: String) -> Result<Vec<DuplicateGroup>, String> {
-    let folder_path = PathBuf::from(&path);
+pub fn find_duplicates(path: String, db_path: State<DbPath>) -> Result<Vec<DuplicateGroup>, String> {
+    find_duplicates_internal(&path, &db_path.0)
+}
+
+fn find_duplicates_internal(path: &str, db_path: &PathBuf) -> Result<Vec<DuplicateGroup>, String> {
+    let folder_path = PathBuf::from(path);
+    let walk_policy = WalkPolicy::load(db_path);
 
     if !folder_path.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
@@ -160,17 +176,20 @@ pub fn find_duplicates(path: String) -> Result<Vec<DuplicateGroup>, String> {
     // Group files by size first (optimization)
     let mut size_groups: HashMap<u64, Vec<PathBuf>> = HashMap::new();
 
-    for entry in WalkDir::new(&folder_path)
+    for entry in walk_policy
+        .walk_dir(&folder_path)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let entry_path = entry.path();
 
-        if !entry_path.is_file() {
+        // Only regular files (or link targets when following) can be duplicates
+        if !entry.file_type().is_file() {
             continue;
         }
 
-        if let Ok(metadata) = fs::metadata(entry_path) {
+        if let Ok(metadata) = entry.metadata() {
             let size = metadata.len();
             if size > 0 {
                 size_groups.entry(size).or_default().push(entry_path.to_path_buf());
@@ -207,6 +226,7 @@ pub fn find_duplicates(path: String) -> Result<Vec<DuplicateGroup>, String> {
                     .unwrap_or_default();
 
                 let category = classify_extension(&extension);
+                let link_target = link_target(p);
 
                 Some(FileInfo {
                     path: p.to_string_lossy().to_string(),
@@ -222,6 +242,8 @@ pub fn find_duplicates(path: String) -> Result<Vec<DuplicateGroup>, String> {
                         .map(|n| n.starts_with('.'))
                         .unwrap_or(false),
                     category,
+                    is_symlink: link_target.is_some(),
+                    link_target,
                 })
             })
             .collect();
@@ -244,10 +266,103 @@ pub fn find_duplicates(path: String) -> Result<Vec<DuplicateGroup>, String> {
     Ok(duplicates)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DedupeResult {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+    pub errors: Vec<String>,
+    pub history_id: i64,
+}
+
+/// Remove duplicate copies in a folder, keeping the earliest modified file of each group.
+/// Every copy is compared byte for byte with the kept file before it is removed.
+pub(crate) fn remove_duplicates_internal(
+    path: &str,
+    db_path: &PathBuf,
+    control: &JobControl,
+) -> Result<DedupeResult, String> {
+    let groups = find_duplicates_internal(path, db_path)?;
+
+    let mut files_removed = 0;
+    let mut bytes_freed = 0;
+    let mut errors: Vec<String> = Vec::new();
+    // (removed path, kept path, time it went to the trash)
+    let mut dedupe_details: Vec<(String, String, i64)> = Vec::new();
+    let mut quarantined: Vec<i64> = Vec::new();
+
+    'groups: for group in groups {
+        let mut files = group.files;
+        files.sort_by(|a, b| a.modified_at.cmp(&b.modified_at).then(a.path.cmp(&b.path)));
+        let Some((kept, copies)) = files.split_first() else {
+            continue;
+        };
+        let kept_path = PathBuf::from(&kept.path);
+
+        for copy in copies {
+            if !control.proceed() {
+                break 'groups;
+            }
+
+            let copy_path = PathBuf::from(&copy.path);
+            match files_identical(&copy_path, &kept_path) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    errors.push(format!("파일 비교 실패 {}: {}", copy.name, e));
+                    continue;
+                }
+            }
+
+            match quarantine::remove_file(db_path, &copy_path, "중복 파일 정리") {
+                Ok(removal) => {
+                    match removal {
+                        Removal::Trashed(deleted_at) => {
+                            dedupe_details.push((copy.path.clone(), kept.path.clone(), deleted_at))
+                        }
+                        Removal::Quarantined(id) => quarantined.push(id),
+                    }
+                    files_removed += 1;
+                    bytes_freed += copy.size;
+                }
+                Err(e) => errors.push(format!("중복 파일 제거 실패 {}: {}", copy.name, e)),
+            }
+        }
+    }
+
+    // Recorded like an organize run, so the same undo restores the copies.
+    // A run that removed nothing leaves no history entry.
+    let history_id = if files_removed > 0 {
+        let details_json = serde_json::json!({
+            "action": "organize",
+            "moves": [],
+            "deduplicated": dedupe_details,
+            "quarantined": quarantined,
+        });
+        database::add_history(
+            db_path,
+            "organize",
+            &format!("중복 파일 정리: {}개 제거 ({})", files_removed, format_size(bytes_freed)),
+            &details_json.to_string(),
+        )
+        .unwrap_or(-1)
+    } else {
+        -1
+    };
+
+    Ok(DedupeResult {
+        files_removed,
+        bytes_freed,
+        errors,
+        history_id,
+    })
+}
+
 /// Find empty folders
 #[tauri::command]
-pub fn find_empty_folders(path: String) -> Result<Vec<String>, String> {
+pub fn find_empty_folders(path: String, db_path: State<DbPath>) -> Result<Vec<String>, String> {
     let folder_path = PathBuf::from(&path);
+    let walk_policy = WalkPolicy::load(&db_path.0);
 
     if !folder_path.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
@@ -255,13 +370,15 @@ pub fn find_empty_folders(path: String) -> Result<Vec<String>, String> {
 
     let mut empty_folders: Vec<String> = Vec::new();
 
-    for entry in WalkDir::new(&folder_path)
+    for entry in walk_policy
+        .walk_dir(&folder_path)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let entry_path = entry.path();
 
-        if entry_path.is_dir() {
+        if entry.file_type().is_dir() {
             if let Ok(mut entries) = fs::read_dir(entry_path) {
                 if entries.next().is_none() {
                     empty_folders.push(entry_path.to_string_lossy().to_string());
@@ -278,8 +395,10 @@ pub fn find_empty_folders(path: String) -> Result<Vec<String>, String> {
 pub fn find_large_files(
     path: String,
     threshold_mb: u64,
+    db_path: State<DbPath>,
 ) -> Result<Vec<FileInfo>, String> {
     let folder_path = PathBuf::from(&path);
+    let walk_policy = WalkPolicy::load(&db_path.0);
     let threshold_bytes = threshold_mb * 1024 * 1024;
 
     if !folder_path.exists() {
@@ -288,17 +407,19 @@ pub fn find_large_files(
 
     let mut large_files: Vec<FileInfo> = Vec::new();
 
-    for entry in WalkDir::new(&folder_path)
+    for entry in walk_policy
+        .walk_dir(&folder_path)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let entry_path = entry.path();
 
-        if !entry_path.is_file() {
+        if !entry.file_type().is_file() {
             continue;
         }
 
-        if let Ok(metadata) = fs::metadata(entry_path) {
+        if let Ok(metadata) = entry.metadata() {
             let size = metadata.len();
             if size >= threshold_bytes {
                 let extension = entry_path
@@ -308,6 +429,7 @@ pub fn find_large_files(
                     .unwrap_or_default();
 
                 let category = classify_extension(&extension);
+                let link_target = link_target(entry_path);
 
                 large_files.push(FileInfo {
                     path: entry_path.to_string_lossy().to_string(),
@@ -326,6 +448,8 @@ pub fn find_large_files(
                         .map(|n| n.starts_with('.'))
                         .unwrap_or(false),
                     category,
+                    is_symlink: link_target.is_some(),
+                    link_target,
                 });
             }
         }
@@ -339,18 +463,37 @@ pub fn find_large_files(
 
 /// Get folder tree structure
 #[tauri::command]
-pub fn get_folder_tree(path: String, max_depth: u32) -> Result<FolderTreeNode, String> {
+pub fn get_folder_tree(
+    path: String,
+    max_depth: u32,
+    db_path: State<DbPath>,
+) -> Result<FolderTreeNode, String> {
     let folder_path = PathBuf::from(&path);
+    let walk_policy = WalkPolicy::load(&db_path.0);
 
     if !folder_path.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
     }
 
-    fn build_tree(path: &PathBuf, current_depth: u32, max_depth: u32) -> Option<FolderTreeNode> {
+    fn build_tree(
+        path: &PathBuf,
+        current_depth: u32,
+        max_depth: u32,
+        walk_policy: &WalkPolicy,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Option<FolderTreeNode> {
         if !path.is_dir() || current_depth > max_depth {
             return None;
         }
 
+        // Followed links can lead back to an ancestor; visit each real folder once
+        if walk_policy.symlinks == SymlinkPolicy::Follow {
+            let real_path = fs::canonicalize(path).ok()?;
+            if !visited.insert(real_path) {
+                return None;
+            }
+        }
+
         let name = path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
@@ -363,14 +506,20 @@ pub fn get_folder_tree(path: String, max_depth: u32) -> Result<FolderTreeNode, S
         if let Ok(entries) = fs::read_dir(path) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let entry_path = entry.path();
-
-                if entry_path.is_dir() {
-                    if let Some(child) = build_tree(&entry_path, current_depth + 1, max_depth) {
+                let metadata = match walk_policy.metadata(&entry_path) {
+                    Some(m) => m,
+                    None => continue,
+                };
+
+                if metadata.is_dir() {
+                    if let Some(child) =
+                        build_tree(&entry_path, current_depth + 1, max_depth, walk_policy, visited)
+                    {
                         total_size += child.size;
                         file_count += child.file_count;
                         children.push(child);
                     }
-                } else if let Ok(metadata) = fs::metadata(&entry_path) {
+                } else {
                     total_size += metadata.len();
                     file_count += 1;
                 }
@@ -390,7 +539,8 @@ pub fn get_folder_tree(path: String, max_depth: u32) -> Result<FolderTreeNode, S
         })
     }
 
-    build_tree(&folder_path, 0, max_depth).ok_or_else(|| "Failed to build folder tree".to_string())
+    let mut visited = HashSet::new();
+    build_tree(&folder_path, 0, max_depth, &walk_policy, &mut visited).ok_or_else(|| "Failed to build folder tree".to_string())
 }
 
 // Helper functions
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::commands::jobs::JobState;
+use crate::commands::quarantine::{self, Removal};
+use crate::database::{self, DbPath};
+use crate::services::archive::{self, ArchiveFormat, ExtractLimits};
+use crate::services::batch_rename;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractResult {
+    pub success: bool,
+    pub extracted_path: String,
+    pub files_extracted: usize,
+    pub total_size: u64,
+    /// Entries left out (links, special files, paths outside the folder)
+    pub skipped: Vec<String>,
+    pub archive_deleted: bool,
+    pub errors: Vec<String>,
+    pub history_id: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateArchiveResult {
+    pub archive_path: String,
+    pub files_added: usize,
+    pub total_size: u64,
+    pub skipped: Vec<String>,
+    pub history_id: i64,
+}
+
+fn path_strings(paths: &[PathBuf]) -> Vec<String> {
+    paths.iter().map(|p| p.to_string_lossy().to_string()).collect()
+}
+
+/// Extracted files with their size and mtime, so undo can leave edited files alone
+fn stamped_files(paths: &[PathBuf]) -> Vec<serde_json::Value> {
+    paths
+        .iter()
+        .map(|path| {
+            let stamp = batch_rename::file_stamp(path);
+            serde_json::json!({
+                "path": path.to_string_lossy(),
+                "size": stamp.map(|(size, _)| size),
+                "modified": stamp.map(|(_, modified)| modified),
+            })
+        })
+        .collect()
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Extract a zip, tar, tar.gz or tar.zst archive into a folder named after it,
+/// next to the archive. With `delete_archive` the archive goes to the quarantine or the
+/// trash afterwards.
+#[tauri::command]
+pub fn extract_archive(
+    archive_path: String,
+    delete_archive: Option<bool>,
+    db_path: State<DbPath>,
+    jobs: State<JobState>,
+) -> Result<ExtractResult, String> {
+    let archive_file = PathBuf::from(&archive_path);
+    if !archive_file.is_file() {
+        return Err("압축 파일이 존재하지 않습니다".to_string());
+    }
+
+    let dest = archive::extract_dir_for(&archive_file).ok_or("지원하지 않는 압축 형식입니다")?;
+
+    let paths = vec![archive_file.clone(), dest.clone()];
+    let (extracted, removal) = jobs.0.run_locked(paths, |_| {
+        let extracted = archive::extract(&archive_file, &dest, &ExtractLimits::default())?;
+        let removal = if delete_archive.unwrap_or(false) {
+            Some(quarantine::remove_file(
+                &db_path.0,
+                &archive_file,
+                "압축 해제 후 삭제",
+            ))
+        } else {
+            None
+        };
+        Ok((extracted, removal))
+    })?;
+
+    let mut errors: Vec<String> = Vec::new();
+    let mut trashed: Vec<(String, i64)> = Vec::new();
+    let mut quarantined: Vec<i64> = Vec::new();
+    match removal {
+        Some(Ok(Removal::Trashed(deleted_at))) => trashed.push((archive_path.clone(), deleted_at)),
+        Some(Ok(Removal::Quarantined(id))) => quarantined.push(id),
+        Some(Err(e)) => errors.push(format!("압축 파일 삭제 실패: {}", e)),
+        None => {}
+    }
+    let archive_deleted = !trashed.is_empty() || !quarantined.is_empty();
+
+    let details_json = serde_json::json!({
+        "action": "extract",
+        "archive_path": archive_path,
+        "extracted_path": dest.to_string_lossy(),
+        "extracted_files": stamped_files(&extracted.files),
+        "created_dirs": path_strings(&extracted.created_dirs),
+        "trashed": trashed,
+        "quarantined": quarantined,
+    });
+    let history_id = database::add_history(
+        &db_path.0,
+        "extract",
+        &format!(
+            "압축 해제: {} ({}개 파일)",
+            display_name(&archive_file),
+            extracted.files.len()
+        ),
+        &details_json.to_string(),
+    )
+    .unwrap_or(-1);
+
+    Ok(ExtractResult {
+        success: errors.is_empty(),
+        extracted_path: dest.to_string_lossy().to_string(),
+        files_extracted: extracted.files.len(),
+        total_size: extracted.total_size,
+        skipped: extracted.skipped,
+        archive_deleted,
+        errors,
+        history_id,
+    })
+}
+
+/// Create an archive from the selected files and folders. The format follows the
+/// extension of `archive_path`; an existing file is never overwritten.
+#[tauri::command]
+pub fn create_archive(
+    paths: Vec<String>,
+    archive_path: String,
+    db_path: State<DbPath>,
+    jobs: State<JobState>,
+) -> Result<CreateArchiveResult, String> {
+    if paths.is_empty() {
+        return Err("압축할 항목을 선택하세요".to_string());
+    }
+
+    let requested = PathBuf::from(&archive_path);
+    if ArchiveFormat::detect(&requested).is_none() {
+        return Err("zip, tar, tar.gz, tar.zst 형식만 만들 수 있습니다".to_string());
+    }
+    let archive_file = archive::unique_archive_path(&requested);
+    let sources: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+
+    // The selection and the new archive stay locked against queued jobs while packing
+    let mut locked = sources.clone();
+    locked.push(archive_file.clone());
+    let packed = jobs
+        .0
+        .run_locked(locked, |_| archive::create(&sources, &archive_file))?;
+
+    let details_json = serde_json::json!({
+        "action": "archive",
+        "archive_path": archive_file.to_string_lossy(),
+        "sources": paths,
+    });
+    let history_id = database::add_history(
+        &db_path.0,
+        "archive",
+        &format!(
+            "압축: {} ({}개 파일)",
+            display_name(&archive_file),
+            packed.files_added
+        ),
+        &details_json.to_string(),
+    )
+    .unwrap_or(-1);
+
+    Ok(CreateArchiveResult {
+        archive_path: archive_file.to_string_lossy().to_string(),
+        files_added: packed.files_added,
+        total_size: packed.total_size,
+        skipped: packed.skipped,
+        history_id,
+    })
+}
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use tauri::State;
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
 
+use crate::commands::jobs::JobState;
+use crate::commands::quarantine::{self, Removal};
 use crate::database::{self, DbPath};
+use crate::services::jobs::JobControl;
+use crate::services::link_policy::{is_special, WalkPolicy};
+use crate::services::settle::{DeferredFile, SettleChecker};
+use crate::services::{mover, shred, trash_bin};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -38,21 +45,97 @@ fn get_unique_path(path: &PathBuf) -> PathBuf {
     }
 }
 
-#[tauri::command]
-pub fn move_file(
-    source: String,
-    dest: String,
-    overwrite: OverwriteStrategy,
-    db_path: State<DbPath>,
-) -> Result<String, String> {
-    let source_path = PathBuf::from(&source);
-    let mut dest_path = PathBuf::from(&dest);
+/// Existing files an overwrite replaced. They go to the quarantine or the trash instead
+/// of being deleted, so undo (or a write that fails afterwards) can bring them back.
+#[derive(Default)]
+struct Replaced {
+    trashed: Vec<(String, i64)>,
+    quarantined: Vec<i64>,
+}
 
-    if !source_path.exists() {
-        return Err(format!("Source file does not exist: {}", source));
+impl Replaced {
+    fn is_empty(&self) -> bool {
+        self.trashed.is_empty() && self.quarantined.is_empty()
+    }
+
+    fn append(&mut self, other: Replaced) {
+        self.trashed.extend(other.trashed);
+        self.quarantined.extend(other.quarantined);
+    }
+
+    /// Put the replaced files back where they were
+    fn restore(&self, db_path: &PathBuf) -> Vec<String> {
+        let entries: Vec<(PathBuf, Option<i64>)> = self
+            .trashed
+            .iter()
+            .map(|(path, deleted_at)| (PathBuf::from(path), Some(*deleted_at)))
+            .collect();
+        let mut errors = trash_bin::restore(&entries);
+        errors.extend(quarantine::restore_quarantined_files(db_path, &self.quarantined));
+        errors
+    }
+
+    /// Add the replaced files to undo data as "trashed" and "quarantined"
+    fn record(&self, undo_data: &mut serde_json::Value) {
+        undo_data["trashed"] = serde_json::json!(self.trashed);
+        undo_data["quarantined"] = serde_json::json!(self.quarantined);
+    }
+}
+
+/// Error of a write that failed after an overwrite. The replaced file is put back;
+/// it stays recorded only if that fails.
+fn write_failed(e: io::Error, replaced: &mut Replaced, db_path: &PathBuf) -> String {
+    let restore_errors = replaced.restore(db_path);
+    if restore_errors.is_empty() {
+        *replaced = Replaced::default();
+    }
+
+    let mut errors = vec![e.to_string()];
+    errors.extend(restore_errors);
+    errors.join("\n")
+}
+
+/// Apply the collision strategy to an existing file: the path to write to, or None to skip
+fn resolve_collision(
+    path: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+    replaced: &mut Replaced,
+) -> io::Result<Option<PathBuf>> {
+    match overwrite {
+        OverwriteStrategy::Overwrite => {
+            match quarantine::remove_file(db_path, path, "덮어쓰기").map_err(io::Error::other)? {
+                Removal::Trashed(deleted_at) => {
+                    replaced.trashed.push((path.to_string_lossy().to_string(), deleted_at))
+                }
+                Removal::Quarantined(id) => replaced.quarantined.push(id),
+            }
+            Ok(Some(path.to_path_buf()))
+        }
+        OverwriteStrategy::Rename => Ok(Some(get_unique_path(&path.to_path_buf()))),
+        OverwriteStrategy::Skip => Ok(None),
     }
+}
 
-    // If dest is a directory, move file into it with same name
+/// Where a file goes after applying the collision strategy
+enum Destination {
+    Ready(PathBuf),
+    /// An existing file was kept (skip strategy)
+    Skipped(PathBuf),
+}
+
+/// Resolve the destination of a move or copy: a directory destination keeps the
+/// file name, and an existing file is overwritten, renamed around or skipped.
+fn prepare_destination(
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+    replaced: &mut Replaced,
+) -> Result<Destination, String> {
+    let mut dest_path = dest.to_path_buf();
+
+    // If dest is a directory, put the file into it with the same name
     if dest_path.is_dir() {
         if let Some(file_name) = source_path.file_name() {
             dest_path = dest_path.join(file_name);
@@ -61,16 +144,11 @@ pub fn move_file(
 
     // Handle existing destination
     if dest_path.exists() {
-        match overwrite {
-            OverwriteStrategy::Overwrite => {
-                fs::remove_file(&dest_path).map_err(|e| e.to_string())?;
-            }
-            OverwriteStrategy::Rename => {
-                dest_path = get_unique_path(&dest_path);
-            }
-            OverwriteStrategy::Skip => {
-                return Ok(dest_path.to_string_lossy().to_string());
-            }
+        let resolved =
+            resolve_collision(&dest_path, overwrite, db_path, replaced).map_err(|e| e.to_string())?;
+        match resolved {
+            Some(path) => dest_path = path,
+            None => return Ok(Destination::Skipped(dest_path)),
         }
     }
 
@@ -79,15 +157,260 @@ pub fn move_file(
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    // Move file
-    fs::rename(&source_path, &dest_path).map_err(|e| e.to_string())?;
+    Ok(Destination::Ready(dest_path))
+}
+
+/// Destination of a folder move or copy. A directory destination keeps the folder name;
+/// an existing folder there is merged into, with the collision strategy applied per file.
+fn directory_destination(
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+) -> Result<PathBuf, String> {
+    let mut dest_path = dest.to_path_buf();
+
+    if dest_path.is_dir() {
+        if let Some(file_name) = source_path.file_name() {
+            dest_path = dest_path.join(file_name);
+        }
+    }
+
+    // Copying a folder next to itself
+    if dest_path == source_path && matches!(overwrite, OverwriteStrategy::Rename) {
+        dest_path = get_unique_path(&dest_path);
+    }
+
+    if dest_path.starts_with(source_path) {
+        return Err("폴더를 자기 자신 안으로 옮기거나 복사할 수 없습니다".to_string());
+    }
+    if dest_path.exists() && !dest_path.is_dir() {
+        return Err(format!("같은 이름의 파일이 이미 있습니다: {}", dest_path.display()));
+    }
+
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    Ok(dest_path)
+}
+
+fn path_strings(paths: &[PathBuf]) -> Vec<String> {
+    paths.iter().map(|p| p.to_string_lossy().to_string()).collect()
+}
+
+/// Error listing the files a tree transfer could not handle, if any
+fn tree_errors(result: &mover::TreeTransfer) -> Result<(), String> {
+    if result.errors.is_empty() {
+        return Ok(());
+    }
+
+    let errors: Vec<String> = result
+        .errors
+        .iter()
+        .map(|(path, e)| format!("{}: {}", path.display(), e))
+        .collect();
+    Err(format!("일부 파일을 처리하지 못했습니다: {}", errors.join(", ")))
+}
+
+/// (source, destination) pairs of a tree transfer as strings, for undo data
+fn transfer_pairs(files: &[(PathBuf, PathBuf)]) -> Vec<(String, String)> {
+    files
+        .iter()
+        .map(|(from, to)| (from.to_string_lossy().to_string(), to.to_string_lossy().to_string()))
+        .collect()
+}
+
+/// Move a folder tree to its destination under `dest`, applying the collision
+/// strategy per file
+fn move_tree_to(
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+    replaced: &mut Replaced,
+    progress: &mut mover::TreeProgress,
+) -> Result<(PathBuf, mover::TreeTransfer), String> {
+    let dest_path = directory_destination(source_path, dest, overwrite)?;
+    let result = mover::move_tree(
+        source_path,
+        &dest_path,
+        &mut |path| resolve_collision(path, overwrite, db_path, replaced),
+        progress,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok((dest_path, result))
+}
+
+/// Copy a folder tree to its destination under `dest`, applying the collision
+/// strategy per file
+fn copy_tree_to(
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+    replaced: &mut Replaced,
+    progress: &mut mover::TreeProgress,
+) -> Result<(PathBuf, mover::TreeTransfer), String> {
+    let dest_path = directory_destination(source_path, dest, overwrite)?;
+    let result = mover::copy_tree(
+        source_path,
+        &dest_path,
+        &mut |path| resolve_collision(path, overwrite, db_path, replaced),
+        progress,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok((dest_path, result))
+}
+
+/// Move a folder tree, falling back to moving file by file across devices
+fn move_directory(
+    app: &AppHandle,
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+) -> Result<String, String> {
+    let mut replaced = Replaced::default();
+    let (dest_path, result) = move_tree_to(
+        source_path,
+        dest,
+        overwrite,
+        db_path,
+        &mut replaced,
+        &mut |current, total, path| {
+            emit_progress(app, "move", current, total, &path.to_string_lossy())
+        },
+    )?;
+    let original_path = source_path.to_string_lossy().to_string();
+    let new_path = dest_path.to_string_lossy().to_string();
+
+    if result.renamed || !result.files.is_empty() || !replaced.is_empty() {
+        let mut undo_data = if result.renamed {
+            serde_json::json!({
+                "original_path": original_path,
+                "new_path": new_path,
+                "action": "move"
+            })
+        } else {
+            serde_json::json!({
+                "original_path": original_path,
+                "new_path": new_path,
+                "moves": transfer_pairs(&result.files),
+                "created_dirs": path_strings(&result.created_dirs),
+                "removed_dirs": path_strings(&result.removed_dirs),
+                "action": "move"
+            })
+        };
+        replaced.record(&mut undo_data);
+
+        database::add_history(
+            db_path,
+            "move",
+            &format!("폴더 이동: {}", source_path.file_name().unwrap_or_default().to_string_lossy()),
+            &undo_data.to_string(),
+        )?;
+    }
+
+    tree_errors(&result)?;
+    Ok(new_path)
+}
+
+/// Copy a folder tree; every file is verified and keeps its timestamps and permissions
+fn copy_directory(
+    app: &AppHandle,
+    source_path: &Path,
+    dest: &Path,
+    overwrite: &OverwriteStrategy,
+    db_path: &PathBuf,
+) -> Result<String, String> {
+    let mut replaced = Replaced::default();
+    let (dest_path, result) = copy_tree_to(
+        source_path,
+        dest,
+        overwrite,
+        db_path,
+        &mut replaced,
+        &mut |current, total, path| {
+            emit_progress(app, "copy", current, total, &path.to_string_lossy())
+        },
+    )?;
+
+    if !result.files.is_empty() || !result.created_dirs.is_empty() || !replaced.is_empty() {
+        let copied_files: Vec<String> =
+            result.files.iter().map(|(_, to)| to.to_string_lossy().to_string()).collect();
+        let mut undo_data = serde_json::json!({
+            "copied_path": dest_path.to_string_lossy(),
+            "copied_files": copied_files,
+            "created_dirs": path_strings(&result.created_dirs),
+            "action": "copy"
+        });
+        replaced.record(&mut undo_data);
+
+        database::add_history(
+            db_path,
+            "copy",
+            &format!("폴더 복사: {}", source_path.file_name().unwrap_or_default().to_string_lossy()),
+            &undo_data.to_string(),
+        )?;
+    }
+
+    tree_errors(&result)?;
+    Ok(dest_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub fn move_file(
+    app: AppHandle,
+    source: String,
+    dest: String,
+    overwrite: OverwriteStrategy,
+    db_path: State<DbPath>,
+) -> Result<String, String> {
+    let source_path = PathBuf::from(&source);
+    let dest_path = PathBuf::from(&dest);
+
+    if !source_path.exists() {
+        return Err(format!("Source file does not exist: {}", source));
+    }
+
+    // A file another app has open or locked, or a folder holding one, is left where it is
+    let settle = SettleChecker::load(&db_path.0);
+    if mover::is_real_dir(&source_path) {
+        let in_use = settle.check_trees_in_use(std::slice::from_ref(&source_path));
+        if let Some((path, reason)) = in_use.iter().min_by(|a, b| a.0.cmp(b.0)) {
+            return Err(format!(
+                "이동할 수 없습니다 ({}): {}",
+                reason.korean_label(),
+                path.display()
+            ));
+        }
+        return move_directory(&app, &source_path, &dest_path, &overwrite, &db_path.0);
+    }
+
+    let in_use = settle.check_in_use(std::slice::from_ref(&source_path));
+    if let Some(reason) = in_use.get(&source_path) {
+        return Err(format!("이동할 수 없습니다 ({}): {}", reason.korean_label(), source));
+    }
+
+    let mut replaced = Replaced::default();
+    let destination =
+        prepare_destination(&source_path, &dest_path, &overwrite, &db_path.0, &mut replaced)?;
+    let dest_path = match destination {
+        Destination::Ready(path) => path,
+        Destination::Skipped(path) => return Ok(path.to_string_lossy().to_string()),
+    };
+
+    // Move file (verified copy + delete across devices)
+    mover::move_file(&source_path, &dest_path)
+        .map_err(|e| write_failed(e, &mut replaced, &db_path.0))?;
 
     // Record in history
-    let undo_data = serde_json::json!({
+    let mut undo_data = serde_json::json!({
         "original_path": source,
         "new_path": dest_path.to_string_lossy(),
         "action": "move"
     });
+    replaced.record(&mut undo_data);
 
     database::add_history(
         &db_path.0,
@@ -101,53 +424,41 @@ pub fn move_file(
 
 #[tauri::command]
 pub fn copy_file(
+    app: AppHandle,
     source: String,
     dest: String,
     overwrite: OverwriteStrategy,
     db_path: State<DbPath>,
 ) -> Result<String, String> {
     let source_path = PathBuf::from(&source);
-    let mut dest_path = PathBuf::from(&dest);
+    let dest_path = PathBuf::from(&dest);
 
     if !source_path.exists() {
         return Err(format!("Source file does not exist: {}", source));
     }
 
-    // If dest is a directory, copy file into it with same name
-    if dest_path.is_dir() {
-        if let Some(file_name) = source_path.file_name() {
-            dest_path = dest_path.join(file_name);
-        }
-    }
-
-    // Handle existing destination
-    if dest_path.exists() {
-        match overwrite {
-            OverwriteStrategy::Overwrite => {
-                fs::remove_file(&dest_path).map_err(|e| e.to_string())?;
-            }
-            OverwriteStrategy::Rename => {
-                dest_path = get_unique_path(&dest_path);
-            }
-            OverwriteStrategy::Skip => {
-                return Ok(dest_path.to_string_lossy().to_string());
-            }
-        }
+    if mover::is_real_dir(&source_path) {
+        return copy_directory(&app, &source_path, &dest_path, &overwrite, &db_path.0);
     }
 
-    // Ensure parent directory exists
-    if let Some(parent) = dest_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
+    let mut replaced = Replaced::default();
+    let destination =
+        prepare_destination(&source_path, &dest_path, &overwrite, &db_path.0, &mut replaced)?;
+    let dest_path = match destination {
+        Destination::Ready(path) => path,
+        Destination::Skipped(path) => return Ok(path.to_string_lossy().to_string()),
+    };
 
-    // Copy file
-    fs::copy(&source_path, &dest_path).map_err(|e| e.to_string())?;
+    // Copy file, verified and keeping timestamps and attributes like folder copies
+    mover::copy_file_verified(&source_path, &dest_path)
+        .map_err(|e| write_failed(e, &mut replaced, &db_path.0))?;
 
     // Record in history
-    let undo_data = serde_json::json!({
+    let mut undo_data = serde_json::json!({
         "copied_path": dest_path.to_string_lossy(),
         "action": "copy"
     });
+    replaced.record(&mut undo_data);
 
     database::add_history(
         &db_path.0,
@@ -159,16 +470,31 @@ pub fn copy_file(
     Ok(dest_path.to_string_lossy().to_string())
 }
 
+/// Delete a file or folder: into the trash, permanently, or (with `shred`) overwritten
+/// before it is unlinked so the contents cannot be recovered from the disk
 #[tauri::command]
-pub fn delete_file(path: String, to_trash: bool, db_path: State<DbPath>) -> Result<(), String> {
+pub fn delete_file(
+    path: String,
+    to_trash: bool,
+    shred: Option<bool>,
+    db_path: State<DbPath>,
+) -> Result<(), String> {
     let file_path = PathBuf::from(&path);
 
-    if !file_path.exists() {
+    if fs::symlink_metadata(&file_path).is_err() {
         return Err(format!("File does not exist: {}", path));
     }
 
+    if shred.unwrap_or(false) {
+        if to_trash {
+            return Err("휴지통으로 보내는 파일은 파쇄할 수 없습니다".to_string());
+        }
+        return shred_file(&file_path, &db_path.0);
+    }
+
+    let mut deleted_at = None;
     if to_trash {
-        trash::delete(&file_path).map_err(|e| e.to_string())?;
+        deleted_at = Some(trash_bin::move_to_trash(&file_path)?);
     } else {
         if file_path.is_dir() {
             fs::remove_dir_all(&file_path).map_err(|e| e.to_string())?;
@@ -177,9 +503,10 @@ pub fn delete_file(path: String, to_trash: bool, db_path: State<DbPath>) -> Resu
         }
     }
 
-    // Record in history (note: undo is limited for permanent delete)
+    // Record in history; the deletion time identifies the trash entry for undo
     let undo_data = serde_json::json!({
         "deleted_path": path,
+        "deleted_at": deleted_at,
         "to_trash": to_trash,
         "action": "delete"
     });
@@ -194,6 +521,32 @@ pub fn delete_file(path: String, to_trash: bool, db_path: State<DbPath>) -> Resu
     Ok(())
 }
 
+fn shred_file(file_path: &Path, db_path: &PathBuf) -> Result<(), String> {
+    shred::shred_path(file_path, shred::DEFAULT_PASSES).map_err(|e| format!("파쇄 실패: {}", e))?;
+
+    // Recorded for the log only: there is nothing to restore
+    let undo_data = serde_json::json!({
+        "deleted_path": file_path.to_string_lossy(),
+        "to_trash": false,
+        "shredded": true,
+        "passes": shred::DEFAULT_PASSES,
+        "warning": shred::SHRED_WARNING,
+        "action": "delete"
+    });
+
+    database::add_history(
+        db_path,
+        "delete",
+        &format!(
+            "파일 파쇄 (복구 불가): {}",
+            file_path.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        &undo_data.to_string(),
+    )?;
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn rename_file(path: String, new_name: String, db_path: State<DbPath>) -> Result<String, String> {
     let file_path = PathBuf::from(&path);
@@ -249,6 +602,336 @@ pub fn create_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult {
+    pub success: bool,
+    pub processed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+    /// Files left in place because another app has them open or locked
+    pub deferred: Vec<DeferredFile>,
+    /// History entry for the whole batch (-1 when nothing was changed)
+    pub history_id: i64,
+}
+
+impl Default for BatchResult {
+    fn default() -> Self {
+        BatchResult {
+            success: true,
+            processed: 0,
+            skipped: 0,
+            failed: 0,
+            errors: Vec::new(),
+            deferred: Vec::new(),
+            history_id: -1,
+        }
+    }
+}
+
+/// Progress of a batch operation, sent as a "batch-progress" event after each file
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct BatchProgress<'a> {
+    operation: &'a str,
+    current: usize,
+    total: usize,
+    path: &'a str,
+}
+
+fn emit_progress(app: &AppHandle, operation: &str, current: usize, total: usize, path: &str) {
+    let _ = app.emit(
+        "batch-progress",
+        BatchProgress {
+            operation,
+            current,
+            total,
+            path,
+        },
+    );
+}
+
+fn file_name_of(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Move several files into a folder, recorded as one undoable history entry
+#[tauri::command]
+pub fn batch_move(
+    app: AppHandle,
+    sources: Vec<String>,
+    dest_dir: String,
+    overwrite: OverwriteStrategy,
+    db_path: State<DbPath>,
+) -> Result<BatchResult, String> {
+    let dest_dir = PathBuf::from(&dest_dir);
+    fs::create_dir_all(&dest_dir).map_err(|e| e.to_string())?;
+
+    let mut result = BatchResult::default();
+    let mut moves: Vec<(String, String)> = Vec::new();
+    // Folders moved file by file (across devices)
+    let mut created_dirs: Vec<PathBuf> = Vec::new();
+    let mut removed_dirs: Vec<PathBuf> = Vec::new();
+    let mut replaced = Replaced::default();
+
+    // Files another app has open or locked are reported instead of moved, and so is
+    // every such file inside a folder, which then stays where it is as a whole
+    let settle = SettleChecker::load(&db_path.0);
+    let (dirs, files): (Vec<PathBuf>, Vec<PathBuf>) = sources
+        .iter()
+        .map(PathBuf::from)
+        .filter(|p| p.is_file() || mover::is_real_dir(p))
+        .partition(|p| mover::is_real_dir(p));
+    let in_use = settle.check_in_use(&files);
+    let in_use_in_dirs = settle.check_trees_in_use(&dirs);
+
+    for (index, source) in sources.iter().enumerate() {
+        let source_path = PathBuf::from(source);
+        if let Some(&reason) = in_use.get(&source_path) {
+            result.deferred.push(DeferredFile { path: source.clone(), reason });
+            emit_progress(&app, "move", index + 1, sources.len(), source);
+            continue;
+        }
+        if mover::is_real_dir(&source_path) {
+            let mut held: Vec<DeferredFile> = in_use_in_dirs
+                .iter()
+                .filter(|(path, _)| path.starts_with(&source_path))
+                .map(|(path, &reason)| DeferredFile {
+                    path: path.to_string_lossy().to_string(),
+                    reason,
+                })
+                .collect();
+            if !held.is_empty() {
+                held.sort_by(|a, b| a.path.cmp(&b.path));
+                result.deferred.extend(held);
+                emit_progress(&app, "move", index + 1, sources.len(), source);
+                continue;
+            }
+        }
+        let mut item_replaced = Replaced::default();
+
+        // Ok(false) when the item was skipped
+        let outcome = if !source_path.exists() {
+            Err("원본 파일이 없습니다".to_string())
+        } else if mover::is_real_dir(&source_path) {
+            move_tree_to(
+                &source_path,
+                &dest_dir,
+                &overwrite,
+                &db_path.0,
+                &mut item_replaced,
+                &mut |_, _, _| {},
+            )
+            .and_then(|(dest_path, transfer)| {
+                if transfer.renamed {
+                    moves.push((source.clone(), dest_path.to_string_lossy().to_string()));
+                } else {
+                    moves.extend(transfer_pairs(&transfer.files));
+                    created_dirs.extend(transfer.created_dirs.iter().cloned());
+                    removed_dirs.extend(transfer.removed_dirs.iter().cloned());
+                }
+                tree_errors(&transfer).map(|_| true)
+            })
+        } else {
+            prepare_destination(&source_path, &dest_dir, &overwrite, &db_path.0, &mut item_replaced)
+                .and_then(|dest| match dest {
+                    Destination::Ready(dest_path) => {
+                        mover::move_file(&source_path, &dest_path)
+                            .map_err(|e| write_failed(e, &mut item_replaced, &db_path.0))?;
+                        moves.push((source.clone(), dest_path.to_string_lossy().to_string()));
+                        Ok(true)
+                    }
+                    Destination::Skipped(_) => Ok(false),
+                })
+        };
+        replaced.append(item_replaced);
+
+        match outcome {
+            Ok(true) => result.processed += 1,
+            Ok(false) => result.skipped += 1,
+            Err(e) => {
+                result.failed += 1;
+                result.errors.push(format!("{}: {}", file_name_of(source), e));
+            }
+        }
+
+        emit_progress(&app, "move", index + 1, sources.len(), source);
+    }
+
+    if !moves.is_empty() || !replaced.is_empty() {
+        let mut undo_data = serde_json::json!({
+            "action": "batch_move",
+            "moves": moves,
+            "created_dirs": path_strings(&created_dirs),
+            "removed_dirs": path_strings(&removed_dirs),
+        });
+        replaced.record(&mut undo_data);
+        result.history_id = database::add_history(
+            &db_path.0,
+            "move",
+            &format!("파일 {}개 이동", moves.len()),
+            &undo_data.to_string(),
+        )
+        .unwrap_or(-1);
+    }
+
+    result.success = result.failed == 0;
+    Ok(result)
+}
+
+/// Copy several files into a folder, recorded as one undoable history entry
+#[tauri::command]
+pub fn batch_copy(
+    app: AppHandle,
+    sources: Vec<String>,
+    dest_dir: String,
+    overwrite: OverwriteStrategy,
+    db_path: State<DbPath>,
+) -> Result<BatchResult, String> {
+    let dest_dir = PathBuf::from(&dest_dir);
+    fs::create_dir_all(&dest_dir).map_err(|e| e.to_string())?;
+
+    let mut result = BatchResult::default();
+    let mut copied_paths: Vec<String> = Vec::new();
+    // Folders a folder copy created, parents first
+    let mut created_dirs: Vec<PathBuf> = Vec::new();
+    let mut replaced = Replaced::default();
+
+    for (index, source) in sources.iter().enumerate() {
+        let source_path = PathBuf::from(source);
+        let mut item_replaced = Replaced::default();
+
+        // Ok(false) when the item was skipped
+        let outcome = if !source_path.exists() {
+            Err("원본 파일이 없습니다".to_string())
+        } else if mover::is_real_dir(&source_path) {
+            copy_tree_to(
+                &source_path,
+                &dest_dir,
+                &overwrite,
+                &db_path.0,
+                &mut item_replaced,
+                &mut |_, _, _| {},
+            )
+            .and_then(|(_, transfer)| {
+                copied_paths
+                    .extend(transfer.files.iter().map(|(_, to)| to.to_string_lossy().to_string()));
+                created_dirs.extend(transfer.created_dirs.iter().cloned());
+                tree_errors(&transfer).map(|_| true)
+            })
+        } else {
+            prepare_destination(&source_path, &dest_dir, &overwrite, &db_path.0, &mut item_replaced)
+                .and_then(|dest| match dest {
+                    Destination::Ready(dest_path) => {
+                        mover::copy_file_verified(&source_path, &dest_path)
+                            .map_err(|e| write_failed(e, &mut item_replaced, &db_path.0))?;
+                        copied_paths.push(dest_path.to_string_lossy().to_string());
+                        Ok(true)
+                    }
+                    Destination::Skipped(_) => Ok(false),
+                })
+        };
+        replaced.append(item_replaced);
+
+        match outcome {
+            Ok(true) => result.processed += 1,
+            Ok(false) => result.skipped += 1,
+            Err(e) => {
+                result.failed += 1;
+                result.errors.push(format!("{}: {}", file_name_of(source), e));
+            }
+        }
+
+        emit_progress(&app, "copy", index + 1, sources.len(), source);
+    }
+
+    if !copied_paths.is_empty() || !created_dirs.is_empty() || !replaced.is_empty() {
+        let mut undo_data = serde_json::json!({
+            "action": "batch_copy",
+            "copied_paths": copied_paths,
+            "created_dirs": path_strings(&created_dirs),
+        });
+        replaced.record(&mut undo_data);
+        result.history_id = database::add_history(
+            &db_path.0,
+            "copy",
+            &format!("파일 {}개 복사", copied_paths.len()),
+            &undo_data.to_string(),
+        )
+        .unwrap_or(-1);
+    }
+
+    result.success = result.failed == 0;
+    Ok(result)
+}
+
+/// Delete several files, recorded as one history entry
+#[tauri::command]
+pub fn batch_delete(
+    app: AppHandle,
+    paths: Vec<String>,
+    to_trash: bool,
+    db_path: State<DbPath>,
+) -> Result<BatchResult, String> {
+    let mut result = BatchResult::default();
+    let mut deleted_paths: Vec<String> = Vec::new();
+    let mut trashed: Vec<(String, i64)> = Vec::new();
+
+    for (index, path) in paths.iter().enumerate() {
+        let file_path = PathBuf::from(path);
+
+        // Links are deleted themselves, so a broken link still counts as existing
+        let outcome = if fs::symlink_metadata(&file_path).is_err() {
+            Err("파일이 없습니다".to_string())
+        } else if to_trash {
+            trash_bin::move_to_trash(&file_path).map(Some)
+        } else if mover::is_real_dir(&file_path) {
+            fs::remove_dir_all(&file_path).map(|_| None).map_err(|e| e.to_string())
+        } else {
+            fs::remove_file(&file_path).map(|_| None).map_err(|e| e.to_string())
+        };
+
+        match outcome {
+            Ok(deleted_at) => {
+                if let Some(deleted_at) = deleted_at {
+                    trashed.push((path.clone(), deleted_at));
+                }
+                deleted_paths.push(path.clone());
+                result.processed += 1;
+            }
+            Err(e) => {
+                result.failed += 1;
+                result.errors.push(format!("{}: {}", file_name_of(path), e));
+            }
+        }
+
+        emit_progress(&app, "delete", index + 1, paths.len(), path);
+    }
+
+    if !deleted_paths.is_empty() {
+        let undo_data = serde_json::json!({
+            "action": "batch_delete",
+            "deleted_paths": deleted_paths,
+            "trashed": trashed,
+            "to_trash": to_trash,
+        });
+        result.history_id = database::add_history(
+            &db_path.0,
+            "delete",
+            &format!("파일 {}개 삭제", deleted_paths.len()),
+            &undo_data.to_string(),
+        )
+        .unwrap_or(-1);
+    }
+
+    result.success = result.failed == 0;
+    Ok(result)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BackupResult {
     pub backup_path: String,
@@ -258,16 +941,27 @@ pub struct BackupResult {
 
 /// Backup desktop folder to a timestamped directory
 #[tauri::command]
-pub fn backup_desktop(backup_location: Option<String>) -> Result<BackupResult, String> {
-    use chrono::Local;
+pub fn backup_desktop(
+    backup_location: Option<String>,
+    db_path: State<DbPath>,
+    jobs: State<JobState>,
+) -> Result<BackupResult, String> {
+    let (desktop, backup_base) = backup_locations(backup_location.clone())?;
+    jobs.0.run_locked(vec![desktop, backup_base], |control| {
+        backup_desktop_internal(backup_location, &db_path.0, control)
+    })
+}
+
+/// The desktop folder and the folder its backups go into
+pub(crate) fn backup_locations(backup_location: Option<String>) -> Result<(PathBuf, PathBuf), String> {
     use directories::UserDirs;
-    use walkdir::WalkDir;
 
     let user_dirs = UserDirs::new().ok_or("Cannot find user directories")?;
 
     // Get desktop path
     let desktop_path = user_dirs.desktop_dir()
-        .ok_or("Cannot find desktop directory")?;
+        .ok_or("Cannot find desktop directory")?
+        .to_path_buf();
 
     // Determine backup location
     let backup_base = match backup_location {
@@ -280,6 +974,19 @@ pub fn backup_desktop(backup_location: Option<String>) -> Result<BackupResult, S
         }
     };
 
+    Ok((desktop_path, backup_base))
+}
+
+/// Back up the desktop; a cancelled job leaves the files copied so far
+pub(crate) fn backup_desktop_internal(
+    backup_location: Option<String>,
+    db_path: &PathBuf,
+    control: &JobControl,
+) -> Result<BackupResult, String> {
+    use chrono::Local;
+
+    let (desktop_path, backup_base) = backup_locations(backup_location)?;
+
     // Create timestamped backup folder name
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
     let backup_folder_name = format!("Desktop_backup_{}", timestamp);
@@ -292,20 +999,39 @@ pub fn backup_desktop(backup_location: Option<String>) -> Result<BackupResult, S
     let mut total_size: u64 = 0;
 
     // Copy all files from desktop
-    for entry in WalkDir::new(&desktop_path)
+    let walk_policy = WalkPolicy::load(db_path);
+    for entry in walk_policy
+        .walk_dir(&desktop_path)
         .min_depth(1)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
+        if !control.proceed() {
+            break;
+        }
+
         let source = entry.path();
         let relative = source.strip_prefix(&desktop_path).map_err(|e| e.to_string())?;
         let dest = backup_path.join(relative);
+        let file_type = entry.file_type();
 
-        if source.is_dir() {
+        if file_type.is_dir() {
             fs::create_dir_all(&dest).map_err(|e| format!("Failed to create directory: {}", e))?;
+        } else if file_type.is_symlink() {
+            // Back up the link itself rather than the target's content
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directory: {}", e))?;
+            }
+            mover::copy_symlink(source, &dest)
+                .map_err(|e| format!("Failed to copy link {}: {}", source.display(), e))?;
+            files_count += 1;
+        } else if is_special(&file_type) {
+            // FIFOs, sockets and devices have no content to back up
+            continue;
         } else {
             // Get file size before copying
-            if let Ok(metadata) = source.metadata() {
+            if let Ok(metadata) = entry.metadata() {
                 total_size += metadata.len();
             }
 
@@ -431,7 +1157,7 @@ pub fn restore_backup(backup_path: String, db_path: State<DbPath>) -> Result<usi
         let relative = source.strip_prefix(&backup_dir).map_err(|e| e.to_string())?;
         let dest = desktop_path.join(relative);
 
-        if source.is_dir() {
+        if entry.file_type().is_dir() {
             fs::create_dir_all(&dest).map_err(|e| format!("Failed to create directory: {}", e))?;
         } else {
             // Ensure parent directory exists
@@ -445,7 +1171,11 @@ pub fn restore_backup(backup_path: String, db_path: State<DbPath>) -> Result<usi
                 fs::rename(&dest, &unique_dest).map_err(|e| e.to_string())?;
             }
 
-            fs::copy(source, &dest).map_err(|e| format!("Failed to restore file: {}", e))?;
+            if entry.file_type().is_symlink() {
+                mover::copy_symlink(source, &dest).map_err(|e| format!("Failed to restore link: {}", e))?;
+            } else {
+                fs::copy(source, &dest).map_err(|e| format!("Failed to restore file: {}", e))?;
+            }
             restored_count += 1;
         }
     }
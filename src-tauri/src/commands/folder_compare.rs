@@ -1,12 +1,19 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use walkdir::WalkDir;
+use std::path::{Path, PathBuf};
+use tauri::State;
 use xxhash_rust::xxh3::xxh3_64;
 
+use crate::commands::jobs::JobState;
 use crate::commands::scanner::FileInfo;
+use crate::database::DbPath;
 use crate::services::classifier::classify_extension;
+use crate::services::filename::to_nfc;
+use crate::services::jobs::JobControl;
+use crate::services::link_policy::{is_special, link_target, WalkPolicy};
+use crate::services::mover;
+use crate::services::settle::{DeferredFile, SettleChecker};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -68,15 +75,30 @@ pub struct MergeResult {
     pub files_copied: usize,
     pub files_skipped: usize,
     pub files_overwritten: usize,
+    pub files_deferred: usize,
+    pub deferred: Vec<DeferredFile>,
     pub bytes_transferred: u64,
     pub errors: Vec<String>,
 }
 
 /// Compare two folders and return differences
 #[tauri::command]
-pub fn compare_folders(source_path: String, target_path: String) -> Result<CompareSummary, String> {
+pub fn compare_folders(
+    source_path: String,
+    target_path: String,
+    db_path: State<DbPath>,
+) -> Result<CompareSummary, String> {
+    compare_folders_internal(source_path, target_path, &db_path.0)
+}
+
+fn compare_folders_internal(
+    source_path: String,
+    target_path: String,
+    db_path: &PathBuf,
+) -> Result<CompareSummary, String> {
     let source = PathBuf::from(&source_path);
     let target = PathBuf::from(&target_path);
+    let walk_policy = WalkPolicy::load(db_path);
 
     if !source.exists() {
         return Err("소스 폴더가 존재하지 않습니다".to_string());
@@ -89,18 +111,21 @@ pub fn compare_folders(source_path: String, target_path: String) -> Result<Compa
     let mut source_files: HashMap<String, (PathBuf, u64, String)> = HashMap::new();
     let mut source_total_size: u64 = 0;
 
-    for entry in WalkDir::new(&source)
+    for entry in walk_policy
+        .walk_dir(&source)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let path = entry.path();
-        if !path.is_file() {
+        // Links are compared as entries of their own unless followed; special files have no content
+        if entry.file_type().is_dir() || is_special(&entry.file_type()) {
             continue;
         }
 
-        if let Ok(metadata) = fs::metadata(path) {
+        if let Ok(metadata) = entry.metadata() {
             let relative = path.strip_prefix(&source)
-                .map(|p| p.to_string_lossy().to_string())
+                .map(|p| to_nfc(&p.to_string_lossy()))
                 .unwrap_or_default();
 
             let size = metadata.len();
@@ -115,18 +140,21 @@ pub fn compare_folders(source_path: String, target_path: String) -> Result<Compa
     let mut target_files: HashMap<String, (PathBuf, u64, String)> = HashMap::new();
     let mut target_total_size: u64 = 0;
 
-    for entry in WalkDir::new(&target)
+    for entry in walk_policy
+        .walk_dir(&target)
         .into_iter()
         .filter_map(|e| e.ok())
+        .filter(|e| walk_policy.admits(e))
     {
         let path = entry.path();
-        if !path.is_file() {
+        // Links are compared as entries of their own unless followed; special files have no content
+        if entry.file_type().is_dir() || is_special(&entry.file_type()) {
             continue;
         }
 
-        if let Ok(metadata) = fs::metadata(path) {
+        if let Ok(metadata) = entry.metadata() {
             let relative = path.strip_prefix(&target)
-                .map(|p| p.to_string_lossy().to_string())
+                .map(|p| to_nfc(&p.to_string_lossy()))
                 .unwrap_or_default();
 
             let size = metadata.len();
@@ -211,6 +239,22 @@ pub fn merge_folders(
     source_path: String,
     target_path: String,
     options: MergeOptions,
+    db_path: State<DbPath>,
+    jobs: State<JobState>,
+) -> Result<MergeResult, String> {
+    let paths = vec![PathBuf::from(&source_path), PathBuf::from(&target_path)];
+    jobs.0.run_locked(paths, |control| {
+        merge_folders_internal(source_path, target_path, &options, &db_path.0, control)
+    })
+}
+
+/// Merge folders; a cancelled job stops between files and keeps the source folder
+pub(crate) fn merge_folders_internal(
+    source_path: String,
+    target_path: String,
+    options: &MergeOptions,
+    db_path: &PathBuf,
+    control: &JobControl,
 ) -> Result<MergeResult, String> {
     let source = PathBuf::from(&source_path);
     let target = PathBuf::from(&target_path);
@@ -223,15 +267,34 @@ pub fn merge_folders(
         fs::create_dir_all(&target).map_err(|e| format!("대상 폴더 생성 실패: {}", e))?;
     }
 
-    let comparison = compare_folders(source_path.clone(), target_path.clone())?;
+    let comparison = compare_folders_internal(source_path.clone(), target_path.clone(), db_path)?;
 
     let mut files_copied = 0;
     let mut files_skipped = 0;
     let mut files_overwritten = 0;
     let mut bytes_transferred: u64 = 0;
     let mut errors: Vec<String> = Vec::new();
+    let mut deferred: Vec<DeferredFile> = Vec::new();
+
+    // Files another app has open or locked are not copied, so the source is kept too
+    let candidates: Vec<PathBuf> = comparison
+        .results
+        .iter()
+        .filter(|r| match r.status {
+            FileStatus::OnlyInSource => options.include_only_in_source,
+            FileStatus::Different => options.include_different,
+            _ => false,
+        })
+        .filter_map(|r| r.source_file.as_ref().map(|f| PathBuf::from(&f.path)))
+        .collect();
+    let in_use = SettleChecker::load(db_path).check_in_use(&candidates);
 
     for result in &comparison.results {
+        if !control.proceed() {
+            errors.push("작업이 취소되었습니다".to_string());
+            break;
+        }
+
         let should_copy = match result.status {
             FileStatus::OnlyInSource => options.include_only_in_source,
             FileStatus::Different => options.include_different,
@@ -250,8 +313,22 @@ pub fn merge_folders(
             Some(f) => f,
             None => continue,
         };
+        if let Some(&reason) = in_use.get(Path::new(&source_file.path)) {
+            deferred.push(DeferredFile {
+                path: source_file.path.clone(),
+                reason,
+            });
+            continue;
+        }
 
-        let target_file_path = target.join(&result.relative_path);
+        // Relative paths are NFC for matching; use the names actually on disk
+        let target_file_path = match &result.target_file {
+            Some(f) => PathBuf::from(&f.path),
+            None => PathBuf::from(&source_file.path)
+                .strip_prefix(&source)
+                .map(|relative| target.join(relative))
+                .unwrap_or_else(|_| target.join(&result.relative_path)),
+        };
 
         // Check if target exists and apply strategy
         let should_overwrite = if target_file_path.exists() {
@@ -322,7 +399,7 @@ pub fn merge_folders(
     }
 
     // Optionally delete source after merge
-    if options.delete_source_after && errors.is_empty() {
+    if options.delete_source_after && errors.is_empty() && deferred.is_empty() {
         if let Err(e) = fs::remove_dir_all(&source) {
             errors.push(format!("소스 폴더 삭제 실패: {}", e));
         }
@@ -333,6 +410,8 @@ pub fn merge_folders(
         files_copied,
         files_skipped,
         files_overwritten,
+        files_deferred: deferred.len(),
+        deferred,
         bytes_transferred,
         errors,
     })
@@ -377,6 +456,7 @@ fn create_file_info(path: &PathBuf, size: u64) -> FileInfo {
         .unwrap_or_default();
 
     let category = classify_extension(&extension);
+    let link_target = link_target(path);
 
     let metadata = fs::metadata(path).ok();
     let created_at = metadata.as_ref()
@@ -412,6 +492,8 @@ fn create_file_info(path: &PathBuf, size: u64) -> FileInfo {
             .map(|n| n.starts_with('.'))
             .unwrap_or(false),
         category,
+        is_symlink: link_target.is_some(),
+        link_target,
     }
 }
 
@@ -431,13 +513,14 @@ fn format_size(size: u64) -> String {
     }
 }
 
-fn copy_file_with_parents(source: &PathBuf, target: &PathBuf) -> Result<(), String> {
+fn copy_file_with_parents(source: &Path, target: &Path) -> Result<(), String> {
     // Create parent directories
     if let Some(parent) = target.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("폴더 생성 실패: {}", e))?;
     }
 
-    fs::copy(source, target).map_err(|e| format!("파일 복사 실패: {}", e))?;
+    // Verified so the source folder can be removed safely afterwards
+    mover::copy_file_verified(source, target).map_err(|e| format!("파일 복사 실패: {}", e))?;
     Ok(())
 }
 
@@ -4,7 +4,10 @@ use std::fs;
 use std::path::PathBuf;
 use tauri::State;
 
+use crate::commands::quarantine;
 use crate::database::{self, DbPath};
+use crate::services::batch_rename::{self, RenameOp};
+use crate::services::{mover, trash_bin};
 
 /// Clean up empty folders after undoing an organize operation
 fn cleanup_empty_folders(undo_data: &serde_json::Value) {
@@ -38,6 +41,223 @@ fn cleanup_empty_folders(undo_data: &serde_json::Value) {
     }
 }
 
+/// Move files back for an array of [original_path, new_path] tuples
+fn undo_moves(moves: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors: Vec<String> = Vec::new();
+
+    if let Some(moves) = moves.as_array() {
+        for move_item in moves {
+            if let Some(arr) = move_item.as_array() {
+                if arr.len() >= 2 {
+                    let original_path = arr[0].as_str().unwrap_or("");
+                    let new_path = arr[1].as_str().unwrap_or("");
+
+                    if !original_path.is_empty() && !new_path.is_empty() {
+                        let new_path_buf = PathBuf::from(new_path);
+                        let original_path_buf = PathBuf::from(original_path);
+
+                        if new_path_buf.exists() {
+                            // Ensure parent directory exists
+                            if let Some(parent) = original_path_buf.parent() {
+                                if let Err(e) = fs::create_dir_all(parent) {
+                                    undo_errors.push(format!("Failed to create dir: {}", e));
+                                    continue;
+                                }
+                            }
+
+                            if let Err(e) = mover::move_file(&new_path_buf, &original_path_buf) {
+                                undo_errors.push(format!("Failed to move {}: {}", new_path, e));
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    undo_errors
+}
+
+/// Delete copied files listed as an array of paths; copies already gone are ignored
+fn remove_copies(paths: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors: Vec<String> = Vec::new();
+
+    for path in paths.as_array().into_iter().flatten().filter_map(|p| p.as_str()) {
+        let copied_path = PathBuf::from(path);
+        if fs::symlink_metadata(&copied_path).is_ok() {
+            if let Err(e) = fs::remove_file(&copied_path) {
+                undo_errors.push(format!("{}: {}", path, e));
+            }
+        }
+    }
+
+    undo_errors
+}
+
+/// Delete extracted files, given as paths or as {path, size, modified} entries.
+/// Files changed since the extraction (size or mtime differ) are kept and reported.
+fn remove_extracted(files: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors: Vec<String> = Vec::new();
+
+    for file in files.as_array().into_iter().flatten() {
+        let Some(path) = file.as_str().or_else(|| file["path"].as_str()) else {
+            continue;
+        };
+        let extracted_path = PathBuf::from(path);
+
+        // Already removed (e.g. a retried undo)
+        let Some((size, modified)) = batch_rename::file_stamp(&extracted_path) else {
+            continue;
+        };
+
+        let changed = file["size"].as_u64().is_some_and(|s| s != size)
+            || file["modified"].as_i64().is_some_and(|m| m != modified);
+        if changed {
+            undo_errors.push(format!("{}: 압축 해제 이후 수정되어 지우지 않았습니다", path));
+            continue;
+        }
+
+        if let Err(e) = fs::remove_file(&extracted_path) {
+            undo_errors.push(format!("{}: {}", path, e));
+        }
+    }
+
+    undo_errors
+}
+
+/// Move quarantined files back, given an array of quarantine ids
+fn restore_quarantined(db_path: &PathBuf, ids: &serde_json::Value) -> Vec<String> {
+    let ids: Vec<i64> = ids.as_array().into_iter().flatten().filter_map(|id| id.as_i64()).collect();
+    quarantine::restore_quarantined_files(db_path, &ids)
+}
+
+/// Put back files an overwrite replaced, recorded as "trashed" and "quarantined"
+fn restore_replaced(db_path: &PathBuf, undo_data: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors = trash_bin::restore(&trash_bin::parse_entries(&undo_data["trashed"]));
+    undo_errors.extend(restore_quarantined(db_path, &undo_data["quarantined"]));
+    undo_errors
+}
+
+/// Remove folders an operation created, deepest first, keeping any that are not empty
+fn remove_created_dirs(dirs: &serde_json::Value) {
+    if let Some(dirs) = dirs.as_array() {
+        for dir in dirs.iter().rev().filter_map(|d| d.as_str()) {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+/// Bring deduplicated files back. Each entry is [original_path, kept_path, deleted_at];
+/// the original is restored from the trash. Where that is not possible (no trash
+/// support, emptied trash, older records without a time) the kept file is copied back,
+/// since the contents were verified identical before the original was trashed.
+fn restore_deduplicated(entries: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors: Vec<String> = Vec::new();
+
+    if let Some(entries) = entries.as_array() {
+        for entry in entries {
+            let original_path = entry[0].as_str().unwrap_or("");
+            let kept_path = entry[1].as_str().unwrap_or("");
+
+            if original_path.is_empty() || kept_path.is_empty() {
+                continue;
+            }
+
+            let original_path_buf = PathBuf::from(original_path);
+            let kept_path_buf = PathBuf::from(kept_path);
+
+            if original_path_buf.exists() {
+                continue;
+            }
+
+            let trash_errors = match entry[2].as_i64() {
+                Some(deleted_at) => {
+                    let trashed = [(original_path_buf.clone(), Some(deleted_at))];
+                    let errors = trash_bin::restore(&trashed);
+                    if errors.is_empty() {
+                        continue;
+                    }
+                    errors
+                }
+                None => Vec::new(),
+            };
+
+            if !kept_path_buf.exists() {
+                undo_errors.extend(trash_errors);
+                undo_errors.push(format!("Kept copy no longer exists: {}", kept_path));
+                continue;
+            }
+
+            if let Some(parent) = original_path_buf.parent() {
+                if let Err(e) = fs::create_dir_all(parent) {
+                    undo_errors.push(format!("Failed to create dir: {}", e));
+                    continue;
+                }
+            }
+
+            if let Err(e) = mover::copy_file_verified(&kept_path_buf, &original_path_buf) {
+                undo_errors.extend(trash_errors);
+                undo_errors.push(format!("Failed to restore {}: {}", original_path, e));
+            }
+        }
+    }
+
+    undo_errors
+}
+
+/// Rename a batch back to the original names, cycle-safe like the rename itself.
+/// Files changed since the rename (size or mtime differ) are left alone and reported.
+fn undo_batch_rename(files: &serde_json::Value) -> Vec<String> {
+    let mut undo_errors: Vec<String> = Vec::new();
+    let mut ops: Vec<RenameOp> = Vec::new();
+
+    for file in files.as_array().into_iter().flatten() {
+        let (Some(original_path), Some(new_path)) =
+            (file["original_path"].as_str(), file["new_path"].as_str())
+        else {
+            continue;
+        };
+        let original_path_buf = PathBuf::from(original_path);
+        let new_path_buf = PathBuf::from(new_path);
+        let name = new_path_buf
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let Some((size, modified)) = batch_rename::file_stamp(&new_path_buf) else {
+            // Already back under its original name (e.g. a retried undo)
+            if !original_path_buf.exists() {
+                undo_errors.push(format!("{}: 파일을 찾을 수 없습니다", name));
+            }
+            continue;
+        };
+
+        let changed = file["size"].as_u64().is_some_and(|s| s != size)
+            || file["modified"].as_i64().is_some_and(|m| m != modified);
+        if changed {
+            undo_errors.push(format!("{}: 이름 변경 이후 수정되어 되돌리지 않았습니다", name));
+            continue;
+        }
+
+        ops.push(RenameOp {
+            from: new_path_buf,
+            to: original_path_buf,
+        });
+    }
+
+    let outcome = batch_rename::execute_batch(&ops);
+    for (path, error) in outcome.errors {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        undo_errors.push(format!("{}: {}", name, error));
+    }
+
+    undo_errors
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HistoryItem {
@@ -71,6 +291,22 @@ pub fn undo_operation(id: i64, db_path: State<DbPath>) -> Result<(), String> {
     let action = undo_data["action"].as_str().unwrap_or("");
 
     match action {
+        "move" if undo_data["moves"].is_array() => {
+            // Folder moved file by file: recreate the source folders it emptied
+            if let Some(folders) = undo_data["removed_dirs"].as_array() {
+                for folder in folders.iter().filter_map(|f| f.as_str()) {
+                    let _ = fs::create_dir_all(folder);
+                }
+            }
+
+            let mut undo_errors = undo_moves(&undo_data["moves"]);
+            remove_created_dirs(&undo_data["created_dirs"]);
+            undo_errors.extend(restore_replaced(&db_path.0, &undo_data));
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
         "move" => {
             let original_path = undo_data["original_path"]
                 .as_str()
@@ -86,7 +322,23 @@ pub fn undo_operation(id: i64, db_path: State<DbPath>) -> Result<(), String> {
                 if let Some(parent) = original_path_buf.parent() {
                     fs::create_dir_all(parent).map_err(|e| e.to_string())?;
                 }
-                fs::rename(&new_path_buf, &original_path_buf).map_err(|e| e.to_string())?;
+                mover::move_file(&new_path_buf, &original_path_buf).map_err(|e| e.to_string())?;
+            }
+
+            // The file the move replaced, if any
+            let undo_errors = restore_replaced(&db_path.0, &undo_data);
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "copy" if undo_data["copied_files"].is_array() => {
+            // Folder copy: remove the copied files, then the folders the copy created
+            let mut undo_errors = remove_copies(&undo_data["copied_files"]);
+            remove_created_dirs(&undo_data["created_dirs"]);
+            undo_errors.extend(restore_replaced(&db_path.0, &undo_data));
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some copies could not be removed: {:?}", undo_errors));
             }
         }
         "copy" => {
@@ -99,6 +351,54 @@ pub fn undo_operation(id: i64, db_path: State<DbPath>) -> Result<(), String> {
             if copied_path_buf.exists() {
                 fs::remove_file(&copied_path_buf).map_err(|e| e.to_string())?;
             }
+
+            // The file the copy replaced, if any
+            let undo_errors = restore_replaced(&db_path.0, &undo_data);
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "batch_move" => {
+            // Folders moved file by file: recreate the source folders they emptied
+            if let Some(folders) = undo_data["removed_dirs"].as_array() {
+                for folder in folders.iter().filter_map(|f| f.as_str()) {
+                    let _ = fs::create_dir_all(folder);
+                }
+            }
+
+            let mut undo_errors = undo_moves(&undo_data["moves"]);
+            remove_created_dirs(&undo_data["created_dirs"]);
+            undo_errors.extend(restore_replaced(&db_path.0, &undo_data));
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "batch_copy" => {
+            let mut undo_errors = remove_copies(&undo_data["copied_paths"]);
+            remove_created_dirs(&undo_data["created_dirs"]);
+            undo_errors.extend(restore_replaced(&db_path.0, &undo_data));
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some copies could not be removed: {:?}", undo_errors));
+            }
+        }
+        "batch_delete" => {
+            let to_trash = undo_data["to_trash"].as_bool().unwrap_or(false);
+            if !to_trash {
+                return Err("Cannot undo permanent delete".to_string());
+            }
+
+            let undo_errors = trash_bin::restore(&trash_bin::parse_entries(&undo_data["trashed"]));
+            if !undo_errors.is_empty() {
+                return Err(undo_errors.join("\n"));
+            }
+        }
+        "rename" if undo_data["files"].is_array() => {
+            let undo_errors = undo_batch_rename(&undo_data["files"]);
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
         }
         "rename" => {
             let original_path = undo_data["original_path"]
@@ -114,52 +414,82 @@ pub fn undo_operation(id: i64, db_path: State<DbPath>) -> Result<(), String> {
                 fs::rename(&new_path_buf, &original_path_buf).map_err(|e| e.to_string())?;
             }
         }
+        "delete" if undo_data["shredded"].as_bool().unwrap_or(false) => {
+            return Err("파쇄된 파일은 복구할 수 없습니다".to_string());
+        }
         "delete" => {
-            // Note: We can only undo trash deletes if supported by the OS
             let to_trash = undo_data["to_trash"].as_bool().unwrap_or(false);
             if !to_trash {
                 return Err("Cannot undo permanent delete".to_string());
             }
-            // Trash undo is OS-specific and not directly supported
-            return Err("Trash undo not implemented. Please restore from trash manually.".to_string());
+
+            let deleted_path = undo_data["deleted_path"]
+                .as_str()
+                .ok_or("Missing deleted_path")?;
+            let entry = serde_json::json!([[deleted_path, undo_data["deleted_at"]]]);
+            let undo_errors = trash_bin::restore(&trash_bin::parse_entries(&entry));
+            if !undo_errors.is_empty() {
+                return Err(undo_errors.join("\n"));
+            }
         }
-        _ => {
-            // Handle organize operation - undo_data is array of [original_path, new_path] tuples
-            if let Some(moves) = undo_data.as_array() {
-                let mut undo_errors: Vec<String> = Vec::new();
-
-                for move_item in moves {
-                    if let Some(arr) = move_item.as_array() {
-                        if arr.len() >= 2 {
-                            let original_path = arr[0].as_str().unwrap_or("");
-                            let new_path = arr[1].as_str().unwrap_or("");
-
-                            if !original_path.is_empty() && !new_path.is_empty() {
-                                let new_path_buf = PathBuf::from(new_path);
-                                let original_path_buf = PathBuf::from(original_path);
-
-                                if new_path_buf.exists() {
-                                    // Ensure parent directory exists
-                                    if let Some(parent) = original_path_buf.parent() {
-                                        if let Err(e) = fs::create_dir_all(parent) {
-                                            undo_errors.push(format!("Failed to create dir: {}", e));
-                                            continue;
-                                        }
-                                    }
-
-                                    if let Err(e) = fs::rename(&new_path_buf, &original_path_buf) {
-                                        // Try copy + delete for cross-device moves
-                                        if let Err(copy_err) = fs::copy(&new_path_buf, &original_path_buf) {
-                                            undo_errors.push(format!("Failed to move {}: {}", new_path, copy_err));
-                                            continue;
-                                        }
-                                        let _ = fs::remove_file(&new_path_buf);
-                                    }
-                                }
-                            }
-                        }
-                    }
+        "organize" => {
+            // Organize operation with move, dedupe and replaced-file records; files move
+            // back before the files they replaced are restored
+            let mut undo_errors = undo_moves(&undo_data["moves"]);
+            undo_errors.extend(restore_deduplicated(&undo_data["deduplicated"]));
+            undo_errors.extend(trash_bin::restore(&trash_bin::parse_entries(&undo_data["trashed"])));
+            undo_errors.extend(remove_copies(&undo_data["copied_paths"]));
+            undo_errors.extend(restore_quarantined(&db_path.0, &undo_data["quarantined"]));
+
+            // Clean up empty category folders
+            cleanup_empty_folders(&undo_data["moves"]);
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "flatten" => {
+            // Folders removed after flattening are recreated even if nothing moves back into them
+            if let Some(folders) = undo_data["removed_folders"].as_array() {
+                for folder in folders.iter().filter_map(|f| f.as_str()) {
+                    let _ = fs::create_dir_all(folder);
                 }
+            }
+
+            // Files moved back first, so the files they replaced can return to their place
+            let mut undo_errors = undo_moves(&undo_data["moves"]);
+            undo_errors.extend(trash_bin::restore(&trash_bin::parse_entries(&undo_data["trashed"])));
+            undo_errors.extend(restore_quarantined(&db_path.0, &undo_data["quarantined"]));
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "extract" => {
+            // Remove the extracted files and folders, then bring back a deleted archive.
+            // Files edited since the extraction stay, and so do the folders holding them.
+            let mut undo_errors = remove_extracted(&undo_data["extracted_files"]);
+            remove_created_dirs(&undo_data["created_dirs"]);
+            undo_errors.extend(restore_replaced(&db_path.0, &undo_data));
+
+            if !undo_errors.is_empty() {
+                return Err(format!("Some files could not be restored: {:?}", undo_errors));
+            }
+        }
+        "archive" => {
+            let archive_path = undo_data["archive_path"]
+                .as_str()
+                .ok_or("Missing archive_path")?;
+
+            // Delete the created archive; the files it was made from were never touched
+            let archive_path_buf = PathBuf::from(archive_path);
+            if archive_path_buf.exists() {
+                fs::remove_file(&archive_path_buf).map_err(|e| e.to_string())?;
+            }
+        }
+        _ => {
+            // Handle legacy organize operation - undo_data is array of [original_path, new_path] tuples
+            if undo_data.is_array() {
+                let undo_errors = undo_moves(&undo_data);
 
                 // Clean up empty category folders
                 cleanup_empty_folders(&undo_data);
@@ -0,0 +1,409 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::analyzer::remove_duplicates_internal;
+use crate::commands::file_ops::{backup_desktop_internal, backup_locations};
+use crate::commands::folder_compare::{merge_folders_internal, MergeOptions};
+use crate::commands::organizer::{execute_organization_internal, OrganizeOptions};
+use crate::database::{self, DbPath};
+use crate::services::jobs::{paths_conflict, JobControl, DEFAULT_MAX_CONCURRENT_JOBS};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub id: i64,
+    pub kind: String,
+    pub params: serde_json::Value,
+    /// "queued", "running", "paused", "completed", "failed" or "cancelled"
+    pub status: String,
+    /// Folders the job works on; jobs with overlapping paths never run at the same time
+    pub paths: Vec<String>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl Job {
+    fn spec(&self) -> Result<JobSpec, String> {
+        JobSpec::parse(&self.kind, self.params.clone())
+    }
+}
+
+/// The operations that can run as background jobs, with their parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "params", rename_all = "lowercase")]
+pub enum JobSpec {
+    #[serde(rename_all = "camelCase")]
+    Organize {
+        source_path: String,
+        options: OrganizeOptions,
+    },
+    #[serde(rename_all = "camelCase")]
+    Backup { backup_location: Option<String> },
+    #[serde(rename_all = "camelCase")]
+    Merge {
+        source_path: String,
+        target_path: String,
+        options: MergeOptions,
+    },
+    #[serde(rename_all = "camelCase")]
+    Dedupe { path: String },
+}
+
+impl JobSpec {
+    fn parse(kind: &str, params: serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(serde_json::json!({ "kind": kind, "params": params }))
+            .map_err(|e| format!("작업 설정이 올바르지 않습니다: {}", e))
+    }
+
+    fn paths(&self) -> Result<Vec<PathBuf>, String> {
+        Ok(match self {
+            JobSpec::Organize { source_path, .. } => vec![PathBuf::from(source_path)],
+            JobSpec::Backup { backup_location } => {
+                let (desktop, backup_base) = backup_locations(backup_location.clone())?;
+                vec![desktop, backup_base]
+            }
+            JobSpec::Merge {
+                source_path,
+                target_path,
+                ..
+            } => vec![PathBuf::from(source_path), PathBuf::from(target_path)],
+            JobSpec::Dedupe { path } => vec![PathBuf::from(path)],
+        })
+    }
+
+    fn execute(&self, db_path: &PathBuf, control: &JobControl) -> Result<serde_json::Value, String> {
+        match self {
+            JobSpec::Organize {
+                source_path,
+                options,
+            } => to_value(execute_organization_internal(db_path, source_path, options, control)?),
+            JobSpec::Backup { backup_location } => {
+                to_value(backup_desktop_internal(backup_location.clone(), db_path, control)?)
+            }
+            JobSpec::Merge {
+                source_path,
+                target_path,
+                options,
+            } => to_value(merge_folders_internal(
+                source_path.clone(),
+                target_path.clone(),
+                options,
+                db_path,
+                control,
+            )?),
+            JobSpec::Dedupe { path } => to_value(remove_duplicates_internal(path, db_path, control)?),
+        }
+    }
+}
+
+fn to_value<T: Serialize>(result: T) -> Result<serde_json::Value, String> {
+    serde_json::to_value(result).map_err(|e| e.to_string())
+}
+
+struct RunningJob {
+    paths: Vec<PathBuf>,
+    control: Arc<JobControl>,
+}
+
+/// Runs queued jobs on worker threads. The queue itself lives in the database,
+/// so jobs queued or interrupted by a restart run again on the next start.
+pub struct JobManager {
+    db_path: PathBuf,
+    app: AppHandle,
+    running: Mutex<HashMap<i64, RunningJob>>,
+    /// Operations run directly (not queued) hold their path locks under negative ids
+    next_direct_id: AtomicI64,
+}
+
+/// Job manager state for Tauri
+pub struct JobState(pub Arc<JobManager>);
+
+impl JobManager {
+    /// Requeue jobs that were running when the app last quit and start the queue
+    pub fn start(db_path: PathBuf, app: AppHandle) -> Arc<Self> {
+        let _ = database::requeue_interrupted_jobs(&db_path);
+
+        let manager = Arc::new(JobManager {
+            db_path,
+            app,
+            running: Mutex::new(HashMap::new()),
+            next_direct_id: AtomicI64::new(-1),
+        });
+        manager.schedule();
+        manager
+    }
+
+    /// Jobs allowed to run at once, from the "max_concurrent_jobs" setting
+    fn max_concurrent(&self) -> usize {
+        database::get_setting(&self.db_path, "max_concurrent_jobs")
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_JOBS)
+            .max(1)
+    }
+
+    /// Start queued jobs in order while there are free slots. A job whose paths overlap
+    /// a running job, or an earlier job still waiting, stays queued so conflicting jobs
+    /// run one after another in the order they were added. A job paused before it
+    /// started keeps its place: later jobs on its folders wait until it has run.
+    fn schedule(self: &Arc<Self>) {
+        let Ok(mut running) = self.running.lock() else {
+            return;
+        };
+        let Ok(mut queued) = database::get_jobs_by_status(&self.db_path, "queued") else {
+            return;
+        };
+        let Ok(paused) = database::get_jobs_by_status(&self.db_path, "paused") else {
+            return;
+        };
+        queued.extend(paused);
+        queued.sort_by_key(|job| job.id);
+        let limit = self.max_concurrent();
+        let mut waiting: Vec<PathBuf> = Vec::new();
+
+        for job in queued {
+            if running.len() >= limit {
+                break;
+            }
+            // Paused while running: it already holds its slot and path locks
+            if running.contains_key(&job.id) {
+                continue;
+            }
+
+            let paths: Vec<PathBuf> = job.paths.iter().map(PathBuf::from).collect();
+            if job.status == "paused"
+                || paths_conflict(&waiting, &paths)
+                || running.values().any(|r| paths_conflict(&r.paths, &paths))
+            {
+                waiting.extend(paths);
+                continue;
+            }
+
+            let spec = match job.spec() {
+                Ok(spec) => spec,
+                Err(e) => {
+                    let _ = database::finish_job(&self.db_path, job.id, "failed", None, Some(&e));
+                    self.notify(job.id);
+                    continue;
+                }
+            };
+            // Cancelled since the queue was read
+            if !database::set_job_status(&self.db_path, job.id, "running").unwrap_or(false) {
+                continue;
+            }
+
+            let control = Arc::new(JobControl::new());
+            running.insert(
+                job.id,
+                RunningJob {
+                    paths,
+                    control: Arc::clone(&control),
+                },
+            );
+            self.notify(job.id);
+
+            let manager = Arc::clone(self);
+            thread::spawn(move || manager.run(job.id, spec, control));
+        }
+    }
+
+    fn run(self: Arc<Self>, id: i64, spec: JobSpec, control: Arc<JobControl>) {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| spec.execute(&self.db_path, &control)))
+            .unwrap_or_else(|_| Err("작업 중 예기치 않은 오류가 발생했습니다".to_string()));
+
+        // A cancelled job keeps whatever it finished before stopping
+        let (status, result, error) = match outcome {
+            Ok(value) if control.is_cancelled() => ("cancelled", Some(value), None),
+            Ok(value) => ("completed", Some(value), None),
+            Err(e) => ("failed", None, Some(e)),
+        };
+        let result = result.map(|v| v.to_string());
+        let _ = database::finish_job(&self.db_path, id, status, result.as_deref(), error.as_deref());
+
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&id);
+        }
+        self.notify(id);
+        self.schedule();
+    }
+
+    fn notify(&self, id: i64) {
+        if let Ok(job) = database::get_job(&self.db_path, id) {
+            let _ = self.app.emit("job-update", &job);
+        }
+    }
+
+    fn control(&self, id: i64) -> Option<Arc<JobControl>> {
+        self.running
+            .lock()
+            .ok()?
+            .get(&id)
+            .map(|r| Arc::clone(&r.control))
+    }
+
+    /// Run an operation right away instead of queueing it, holding locks on its paths
+    /// so queued jobs on the same folders wait for it. Fails while a running or paused
+    /// job works on those folders.
+    pub fn run_locked<T>(
+        self: &Arc<Self>,
+        paths: Vec<PathBuf>,
+        task: impl FnOnce(&JobControl) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let id = self.next_direct_id.fetch_sub(1, Ordering::Relaxed);
+        let control = Arc::new(JobControl::new());
+        {
+            let mut running = self
+                .running
+                .lock()
+                .map_err(|_| "작업 상태를 확인할 수 없습니다".to_string())?;
+            if running.values().any(|r| paths_conflict(&r.paths, &paths)) {
+                return Err("같은 폴더에서 다른 작업이 실행 중입니다".to_string());
+            }
+            running.insert(
+                id,
+                RunningJob {
+                    paths,
+                    control: Arc::clone(&control),
+                },
+            );
+        }
+
+        // Released even if the task panics
+        let _lock = DirectLock {
+            manager: Arc::clone(self),
+            id,
+        };
+        task(&control)
+    }
+
+    pub fn enqueue(self: &Arc<Self>, spec: &JobSpec) -> Result<Job, String> {
+        let paths: Vec<String> = spec
+            .paths()?
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let value = serde_json::to_value(spec).map_err(|e| e.to_string())?;
+
+        let id = database::add_job(
+            &self.db_path,
+            value["kind"].as_str().unwrap_or_default(),
+            &value["params"].to_string(),
+            &serde_json::to_string(&paths).map_err(|e| e.to_string())?,
+        )?;
+        self.schedule();
+
+        database::get_job(&self.db_path, id)
+    }
+
+    /// Pause a job: a running job stops before its next file but keeps its slot and
+    /// path locks; a queued job is held back until it is resumed
+    pub fn pause(&self, id: i64) -> Result<(), String> {
+        let job = database::get_job(&self.db_path, id)?;
+        if job.status != "queued" && job.status != "running" {
+            return Err("대기 중이거나 실행 중인 작업만 일시정지할 수 있습니다".to_string());
+        }
+
+        if !database::set_job_status(&self.db_path, id, "paused")? {
+            return Err("이미 끝난 작업입니다".to_string());
+        }
+        if let Some(control) = self.control(id) {
+            control.pause();
+        }
+        self.notify(id);
+        Ok(())
+    }
+
+    pub fn resume(self: &Arc<Self>, id: i64) -> Result<(), String> {
+        let job = database::get_job(&self.db_path, id)?;
+        if job.status != "paused" {
+            return Err("일시정지된 작업이 아닙니다".to_string());
+        }
+
+        let control = self.control(id);
+        let status = if control.is_some() { "running" } else { "queued" };
+        if !database::set_job_status(&self.db_path, id, status)? {
+            return Err("이미 끝난 작업입니다".to_string());
+        }
+        if let Some(control) = control {
+            control.resume();
+        }
+        self.notify(id);
+        self.schedule();
+        Ok(())
+    }
+
+    /// Cancel a job: a running job stops before its next file, a waiting job never starts
+    pub fn cancel(&self, id: i64) -> Result<(), String> {
+        let job = database::get_job(&self.db_path, id)?;
+        if !matches!(job.status.as_str(), "queued" | "running" | "paused") {
+            return Err("이미 끝난 작업입니다".to_string());
+        }
+
+        match self.control(id) {
+            // The worker records the final status once it stops
+            Some(control) => control.cancel(),
+            None => {
+                database::finish_job(&self.db_path, id, "cancelled", None, None)?;
+                self.notify(id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Path locks of an operation started with `run_locked`
+struct DirectLock {
+    manager: Arc<JobManager>,
+    id: i64,
+}
+
+impl Drop for DirectLock {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.manager.running.lock() {
+            running.remove(&self.id);
+        }
+        // Jobs held back by the lock can start now
+        self.manager.schedule();
+    }
+}
+
+/// Queue an organize, backup, merge or dedupe job
+#[tauri::command]
+pub fn enqueue_job(
+    kind: String,
+    params: serde_json::Value,
+    jobs: State<JobState>,
+) -> Result<Job, String> {
+    let spec = JobSpec::parse(&kind, params)?;
+    jobs.0.enqueue(&spec)
+}
+
+#[tauri::command]
+pub fn list_jobs(db_path: State<DbPath>) -> Result<Vec<Job>, String> {
+    database::get_jobs(&db_path.0)
+}
+
+#[tauri::command]
+pub fn pause_job(id: i64, jobs: State<JobState>) -> Result<(), String> {
+    jobs.0.pause(id)
+}
+
+#[tauri::command]
+pub fn resume_job(id: i64, jobs: State<JobState>) -> Result<(), String> {
+    jobs.0.resume(id)
+}
+
+#[tauri::command]
+pub fn cancel_job(id: i64, jobs: State<JobState>) -> Result<(), String> {
+    jobs.0.cancel(id)
+}
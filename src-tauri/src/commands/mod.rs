@@ -1,8 +1,13 @@
 pub mod analyzer;
+pub mod archive;
 pub mod file_ops;
 pub mod folder_compare;
 pub mod history;
+pub mod jobs;
 pub mod organizer;
+pub mod pins;
+pub mod profiles;
+pub mod quarantine;
 pub mod renamer;
 pub mod rules;
 pub mod scanner;
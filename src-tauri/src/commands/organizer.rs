@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
+use crate::commands::jobs::JobState;
+use crate::commands::pins::PinMatcher;
+use crate::commands::quarantine::{self, Removal};
 use crate::commands::scanner::FileInfo;
 use crate::database::DbPath;
 use crate::services::classifier::{classify_extension, get_category_folder, FileCategory};
+use crate::services::date_source::{resolve_date_folder, DateSource};
+use crate::services::hasher::files_identical;
+use crate::services::jobs::JobControl;
+use crate::services::link_policy::{link_target, WalkPolicy};
+use crate::services::mover;
+use crate::services::settle::{DeferredFile, SettleChecker};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +25,17 @@ pub struct OrganizePreview {
     pub files: Vec<FileInfo>,
     pub destination_folder: String,
     pub file_count: usize,
+    pub file_dates: Vec<FileDatePreview>,
+    pub deferred: Vec<DeferredFile>,
+}
+
+/// Date subfolder a file would go into, and which date source produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDatePreview {
+    pub path: String,
+    pub date_folder: String,
+    pub date_source: Option<DateSource>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,7 +43,9 @@ pub struct OrganizePreview {
 pub struct OrganizeOptions {
     pub create_date_subfolders: bool,
     pub date_format: String,
-    pub handle_duplicates: String, // "overwrite", "rename", "skip"
+    #[serde(default)]
+    pub date_source: DateSource,
+    pub handle_duplicates: String, // "overwrite", "rename", "skip", "dedupe"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +54,9 @@ pub struct OrganizeResult {
     pub success: bool,
     pub files_moved: usize,
     pub files_skipped: usize,
+    pub files_deduplicated: usize,
+    pub files_deferred: usize,
+    pub deferred: Vec<DeferredFile>,
     pub errors: Vec<String>,
     pub history_id: i64,
 }
@@ -39,15 +64,25 @@ pub struct OrganizeResult {
 /// Preview organization - shows what will happen without actually moving files
 #[tauri::command]
 pub fn preview_organization(
+    db_state: State<DbPath>,
     source_path: String,
+    options: Option<OrganizeOptions>,
 ) -> Result<Vec<OrganizePreview>, String> {
     let source = PathBuf::from(&source_path);
+    let pins = PinMatcher::load(&db_state.0);
+    let settle = SettleChecker::load(&db_state.0);
+    let walk_policy = WalkPolicy::load(&db_state.0);
 
     if !source.exists() {
         return Err("소스 경로가 존재하지 않습니다".to_string());
     }
 
     let mut categories: HashMap<FileCategory, Vec<FileInfo>> = HashMap::new();
+    let mut file_dates: HashMap<FileCategory, Vec<FileDatePreview>> = HashMap::new();
+    let mut kept: Vec<FileInfo> = Vec::new();
+    let mut deferred_files: Vec<FileInfo> = Vec::new();
+    let mut deferred: Vec<DeferredFile> = Vec::new();
+    let deferred_reasons = settle.check_dir(&source);
 
     // Read directory entries
     let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
@@ -55,10 +90,10 @@ pub fn preview_organization(
     for entry in entries.flatten() {
         let path = entry.path();
 
-        // Skip directories and hidden files
-        if path.is_dir() {
+        // Skip folders, links to folders, hidden files and entries the link policy leaves out
+        let Some(metadata) = walk_policy.file_metadata(&path) else {
             continue;
-        }
+        };
 
         let file_name = path.file_name()
             .and_then(|n| n.to_str())
@@ -76,7 +111,8 @@ pub fn preview_organization(
 
         let category = classify_extension(&extension);
 
-        let metadata = fs::metadata(&path).ok();
+        let link_target = link_target(&path);
+        let metadata = Some(metadata);
         let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
 
         let file_info = FileInfo {
@@ -90,8 +126,36 @@ pub fn preview_organization(
             is_directory: false,
             is_hidden: false,
             category: category.clone(),
+            is_symlink: link_target.is_some(),
+            link_target,
         };
 
+        // Pinned files stay where they are
+        if pins.is_pinned(&path) {
+            kept.push(file_info);
+            continue;
+        }
+
+        // Downloads and files still being written are left for a later run
+        if let Some(reason) = deferred_reasons.get(&path) {
+            deferred.push(DeferredFile {
+                path: file_info.path.clone(),
+                reason: *reason,
+            });
+            deferred_files.push(file_info);
+            continue;
+        }
+
+        if let Some(opts) = options.as_ref().filter(|o| o.create_date_subfolders) {
+            let (date_folder, date_source) =
+                resolve_date_folder(&path, metadata.as_ref(), opts.date_source, &opts.date_format);
+            file_dates.entry(category.clone()).or_default().push(FileDatePreview {
+                path: file_info.path.clone(),
+                date_folder,
+                date_source,
+            });
+        }
+
         categories.entry(category).or_default().push(file_info);
     }
 
@@ -102,6 +166,7 @@ pub fn preview_organization(
             let folder_name = get_category_folder(&category);
             let dest_path = source.join(folder_name);
             let file_count = files.len();
+            let dates = file_dates.remove(&category).unwrap_or_default();
 
             OrganizePreview {
                 category: format!("{:?}", category).to_lowercase(),
@@ -109,6 +174,8 @@ pub fn preview_organization(
                 files,
                 destination_folder: dest_path.to_string_lossy().to_string(),
                 file_count,
+                file_dates: dates,
+                deferred: Vec::new(),
             }
         })
         .filter(|p| p.file_count > 0)
@@ -117,6 +184,33 @@ pub fn preview_organization(
     // Sort by file count descending
     previews.sort_by(|a, b| b.file_count.cmp(&a.file_count));
 
+    // Pinned files are listed last as "kept"
+    if !kept.is_empty() {
+        let file_count = kept.len();
+        previews.push(OrganizePreview {
+            category: "kept".to_string(),
+            category_label: "고정됨 (유지)".to_string(),
+            files: kept,
+            destination_folder: source.to_string_lossy().to_string(),
+            file_count,
+            file_dates: Vec::new(),
+            deferred: Vec::new(),
+        });
+    }
+
+    if !deferred_files.is_empty() {
+        let file_count = deferred_files.len();
+        previews.push(OrganizePreview {
+            category: "deferred".to_string(),
+            category_label: "보류됨".to_string(),
+            files: deferred_files,
+            destination_folder: source.to_string_lossy().to_string(),
+            file_count,
+            file_dates: Vec::new(),
+            deferred,
+        });
+    }
+
     Ok(previews)
 }
 
@@ -124,31 +218,58 @@ pub fn preview_organization(
 #[tauri::command]
 pub fn execute_organization(
     db_state: State<DbPath>,
+    jobs: State<JobState>,
     source_path: String,
     options: OrganizeOptions,
 ) -> Result<OrganizeResult, String> {
-    let source = PathBuf::from(&source_path);
-    let db_path = &db_state.0;
+    jobs.0.run_locked(vec![PathBuf::from(&source_path)], |control| {
+        execute_organization_internal(&db_state.0, &source_path, &options, control)
+    })
+}
+
+/// Organize a folder; stops between files when the job is cancelled
+pub(crate) fn execute_organization_internal(
+    db_path: &PathBuf,
+    source_path: &str,
+    options: &OrganizeOptions,
+    control: &JobControl,
+) -> Result<OrganizeResult, String> {
+    let source = PathBuf::from(source_path);
+    let pins = PinMatcher::load(db_path);
+    let walk_policy = WalkPolicy::load(db_path);
 
     if !source.exists() {
         return Err("소스 경로가 존재하지 않습니다".to_string());
     }
 
+    let deferred_reasons = SettleChecker::load(db_path).check_dir(&source);
+    let mut deferred: Vec<DeferredFile> = Vec::new();
+
     let mut files_moved = 0;
     let mut files_skipped = 0;
+    let mut files_deduplicated = 0;
     let mut errors: Vec<String> = Vec::new();
     let mut move_details: Vec<(String, String)> = Vec::new();
+    // (removed path, kept path, time it went to the trash)
+    let mut dedupe_details: Vec<(String, String, i64)> = Vec::new();
+    // Files an overwrite replaced, kept so undo can bring them back
+    let mut trashed: Vec<(String, i64)> = Vec::new();
+    let mut quarantined: Vec<i64> = Vec::new();
 
     // Read directory entries
     let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
 
     for entry in entries.flatten() {
+        if !control.proceed() {
+            break;
+        }
+
         let path = entry.path();
 
-        // Skip directories and hidden files
-        if path.is_dir() {
+        // Skip folders, links to folders, hidden files and entries the link policy leaves out
+        let Some(metadata) = walk_policy.file_metadata(&path) else {
             continue;
-        }
+        };
 
         let file_name = path.file_name()
             .and_then(|n| n.to_str())
@@ -159,6 +280,20 @@ pub fn execute_organization(
             continue;
         }
 
+        // Never move pinned files
+        if pins.is_pinned(&path) {
+            continue;
+        }
+
+        // Leave downloads and files still being written for a later run
+        if let Some(reason) = deferred_reasons.get(&path) {
+            deferred.push(DeferredFile {
+                path: path.to_string_lossy().to_string(),
+                reason: *reason,
+            });
+            continue;
+        }
+
         let extension = path.extension()
             .and_then(|e| e.to_str())
             .map(|e| format!(".{}", e.to_lowercase()))
@@ -172,8 +307,8 @@ pub fn execute_organization(
 
         // Optionally create date subfolder
         if options.create_date_subfolders {
-            let metadata = fs::metadata(&path).ok();
-            let date_folder = get_date_folder(&metadata, &options.date_format);
+            let (date_folder, _) =
+                resolve_date_folder(&path, Some(&metadata), options.date_source, &options.date_format);
             dest_folder = dest_folder.join(date_folder);
         }
 
@@ -195,8 +330,52 @@ pub fn execute_organization(
                 "rename" => {
                     dest_path = get_unique_path(&dest_path);
                 }
-                "overwrite" => {
-                    // Will overwrite
+                "overwrite" if dest_path.is_file() => {
+                    // The replaced file goes to the trash or quarantine so undo can bring it back
+                    match quarantine::remove_file(db_path, &dest_path, "정리 덮어쓰기") {
+                        Ok(Removal::Trashed(deleted_at)) => {
+                            trashed.push((dest_path.to_string_lossy().to_string(), deleted_at))
+                        }
+                        Ok(Removal::Quarantined(id)) => quarantined.push(id),
+                        Err(e) => {
+                            errors.push(format!("덮어쓸 파일 제거 실패 {}: {}", file_name, e));
+                            files_skipped += 1;
+                            continue;
+                        }
+                    }
+                }
+                "dedupe" => {
+                    // Identical content: remove the incoming copy, keep the existing file
+                    match files_identical(&path, &dest_path) {
+                        Ok(true) => {
+                            match quarantine::remove_file(db_path, &path, "중복 파일 정리") {
+                                Ok(Removal::Trashed(deleted_at)) => {
+                                    dedupe_details.push((
+                                        path.to_string_lossy().to_string(),
+                                        dest_path.to_string_lossy().to_string(),
+                                        deleted_at,
+                                    ));
+                                    files_deduplicated += 1;
+                                }
+                                Ok(Removal::Quarantined(id)) => {
+                                    quarantined.push(id);
+                                    files_deduplicated += 1;
+                                }
+                                Err(e) => {
+                                    errors.push(format!("중복 파일 제거 실패 {}: {}", file_name, e));
+                                }
+                            }
+                            continue;
+                        }
+                        Ok(false) => {
+                            // Different content: keep both
+                            dest_path = get_unique_path(&dest_path);
+                        }
+                        Err(e) => {
+                            errors.push(format!("파일 비교 실패 {}: {}", file_name, e));
+                            continue;
+                        }
+                    }
                 }
                 _ => {
                     files_skipped += 1;
@@ -206,7 +385,7 @@ pub fn execute_organization(
         }
 
         // Move the file
-        match fs::rename(&path, &dest_path) {
+        match mover::move_file(&path, &dest_path) {
             Ok(_) => {
                 move_details.push((
                     path.to_string_lossy().to_string(),
@@ -215,44 +394,353 @@ pub fn execute_organization(
                 files_moved += 1;
             }
             Err(e) => {
-                // Try copy + delete if rename fails (cross-device)
-                match fs::copy(&path, &dest_path) {
-                    Ok(_) => {
-                        if let Err(del_err) = fs::remove_file(&path) {
-                            errors.push(format!("원본 삭제 실패 {}: {}", file_name, del_err));
-                        }
-                        move_details.push((
-                            path.to_string_lossy().to_string(),
-                            dest_path.to_string_lossy().to_string(),
-                        ));
-                        files_moved += 1;
-                    }
-                    Err(copy_err) => {
-                        errors.push(format!("파일 이동 실패 {}: {}", file_name, copy_err));
-                    }
-                }
+                errors.push(format!("파일 이동 실패 {}: {}", file_name, e));
             }
         }
     }
 
     // Record history
-    let details_json = serde_json::to_string(&move_details).unwrap_or_default();
+    let details_json = serde_json::json!({
+        "action": "organize",
+        "moves": move_details,
+        "deduplicated": dedupe_details,
+        "trashed": trashed,
+        "quarantined": quarantined,
+    });
+    let description = if files_deduplicated > 0 {
+        format!(
+            "바탕화면 자동 정리: {}개 파일 이동, {}개 중복 제거",
+            files_moved, files_deduplicated
+        )
+    } else {
+        format!("바탕화면 자동 정리: {}개 파일 이동", files_moved)
+    };
     let history_id = crate::database::add_history(
         db_path,
         "organize",
-        &format!("바탕화면 자동 정리: {}개 파일 이동", files_moved),
-        &details_json,
+        &description,
+        &details_json.to_string(),
     ).unwrap_or(-1);
 
     Ok(OrganizeResult {
         success: errors.is_empty(),
         files_moved,
         files_skipped,
+        files_deduplicated,
+        files_deferred: deferred.len(),
+        deferred,
         errors,
         history_id,
     })
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenOptions {
+    /// Folder depth files are moved up to (0 = the folder itself)
+    #[serde(default)]
+    pub target_depth: usize,
+    pub handle_duplicates: String, // "overwrite", "rename", "skip"
+    #[serde(default = "default_true")]
+    pub remove_empty_folders: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One planned move of a flatten operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenMove {
+    pub from: String,
+    pub to: String,
+    pub action: String, // "move", "rename", "overwrite", "skip" or "kept" (pinned)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenResult {
+    pub success: bool,
+    pub files_moved: usize,
+    pub files_skipped: usize,
+    pub files_deferred: usize,
+    pub deferred: Vec<DeferredFile>,
+    pub folders_removed: usize,
+    pub errors: Vec<String>,
+    pub history_id: i64,
+}
+
+/// Preview flattening - lists every move without touching files
+#[tauri::command]
+pub fn preview_flatten(
+    db_state: State<DbPath>,
+    source_path: String,
+    options: FlattenOptions,
+) -> Result<Vec<FlattenMove>, String> {
+    let walk_policy = WalkPolicy::load(&db_state.0);
+    let pins = PinMatcher::load(&db_state.0);
+    plan_flatten(&PathBuf::from(&source_path), &options, &walk_policy, &pins)
+}
+
+/// Move files from nested subfolders up to the target depth and remove emptied folders
+#[tauri::command]
+pub fn flatten_folder(
+    db_state: State<DbPath>,
+    jobs: State<JobState>,
+    source_path: String,
+    options: FlattenOptions,
+) -> Result<FlattenResult, String> {
+    jobs.0.run_locked(vec![PathBuf::from(&source_path)], |_| {
+        flatten_folder_internal(&db_state.0, &source_path, &options)
+    })
+}
+
+fn flatten_folder_internal(
+    db_path: &PathBuf,
+    source_path: &str,
+    options: &FlattenOptions,
+) -> Result<FlattenResult, String> {
+    let source = PathBuf::from(source_path);
+    let walk_policy = WalkPolicy::load(db_path);
+    let pins = PinMatcher::load(db_path);
+    let plan = plan_flatten(&source, options, &walk_policy, &pins)?;
+
+    let mut files_moved = 0;
+    let mut files_skipped = 0;
+    let mut errors: Vec<String> = Vec::new();
+    let mut move_details: Vec<(String, String)> = Vec::new();
+    let mut trashed: Vec<(String, i64)> = Vec::new();
+    let mut quarantined: Vec<i64> = Vec::new();
+    let mut deferred: Vec<DeferredFile> = Vec::new();
+
+    // Files another app has open or locked stay in their subfolder
+    let pending: Vec<PathBuf> = plan
+        .iter()
+        .filter(|item| item.action != "skip" && item.action != "kept")
+        .map(|item| PathBuf::from(&item.from))
+        .collect();
+    let in_use = SettleChecker::load(db_path).check_in_use(&pending);
+
+    for item in &plan {
+        // Pinned files stay in their subfolder
+        if item.action == "kept" {
+            continue;
+        }
+        if item.action == "skip" {
+            files_skipped += 1;
+            continue;
+        }
+        if let Some(&reason) = in_use.get(Path::new(&item.from)) {
+            deferred.push(DeferredFile {
+                path: item.from.clone(),
+                reason,
+            });
+            continue;
+        }
+
+        // The file being replaced goes to the trash or quarantine so undo can bring it back
+        if item.action == "overwrite" && Path::new(&item.to).is_file() {
+            let replaced = Path::new(&item.to);
+            match quarantine::remove_file(db_path, replaced, "폴더 평탄화 덮어쓰기") {
+                Ok(Removal::Trashed(deleted_at)) => trashed.push((item.to.clone(), deleted_at)),
+                Ok(Removal::Quarantined(id)) => quarantined.push(id),
+                Err(e) => {
+                    errors.push(format!("덮어쓸 파일 제거 실패 {}: {}", item.to, e));
+                    files_skipped += 1;
+                    continue;
+                }
+            }
+        }
+
+        match mover::move_file(&PathBuf::from(&item.from), &PathBuf::from(&item.to)) {
+            Ok(_) => {
+                move_details.push((item.from.clone(), item.to.clone()));
+                files_moved += 1;
+            }
+            Err(e) => {
+                errors.push(format!("파일 이동 실패 {}: {}", item.from, e));
+                files_skipped += 1;
+            }
+        }
+    }
+
+    // Remove the folders this flatten emptied, deepest first. Only folders that held
+    // moved files (and their parents below the target depth) are candidates, so empty
+    // folders the user already had are left alone.
+    let mut removed_folders: Vec<String> = Vec::new();
+    if options.remove_empty_folders {
+        let mut folders: Vec<PathBuf> = move_details
+            .iter()
+            .flat_map(|(from, _)| {
+                Path::new(from)
+                    .ancestors()
+                    .skip(1)
+                    .take_while(|dir| {
+                        dir.strip_prefix(&source)
+                            .map(|r| r.components().count() > options.target_depth)
+                            .unwrap_or(false)
+                    })
+                    .map(Path::to_path_buf)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        folders.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+        for folder in folders {
+            if fs::remove_dir(&folder).is_ok() {
+                removed_folders.push(folder.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    // Record history; a run that changed nothing leaves no entry
+    let history_id = if move_details.is_empty() && trashed.is_empty() && quarantined.is_empty() {
+        -1
+    } else {
+        let details_json = serde_json::json!({
+            "action": "flatten",
+            "moves": move_details,
+            "removed_folders": removed_folders,
+            "trashed": trashed,
+            "quarantined": quarantined,
+        });
+        crate::database::add_history(
+            db_path,
+            "organize",
+            &format!(
+                "폴더 평탄화: {}개 파일 이동, {}개 폴더 삭제",
+                files_moved,
+                removed_folders.len()
+            ),
+            &details_json.to_string(),
+        )
+        .unwrap_or(-1)
+    };
+
+    Ok(FlattenResult {
+        success: errors.is_empty(),
+        files_moved,
+        files_skipped,
+        files_deferred: deferred.len(),
+        deferred,
+        folders_removed: removed_folders.len(),
+        errors,
+        history_id,
+    })
+}
+
+/// Plan the moves of a flatten operation. Files already at or above the target
+/// depth stay where they are, and pinned files are listed as "kept"; collisions
+/// between planned moves are resolved too.
+fn plan_flatten(
+    source: &PathBuf,
+    options: &FlattenOptions,
+    walk_policy: &WalkPolicy,
+    pins: &PinMatcher,
+) -> Result<Vec<FlattenMove>, String> {
+    if !source.is_dir() {
+        return Err("소스 경로가 존재하지 않습니다".to_string());
+    }
+
+    let mut plan: Vec<FlattenMove> = Vec::new();
+    let mut planned: HashSet<PathBuf> = HashSet::new();
+
+    let mut entries: Vec<walkdir::DirEntry> = walk_policy
+        .walk_dir(source)
+        .min_depth(options.target_depth + 2)
+        .into_iter()
+        .flatten()
+        .filter(|e| walk_policy.admits(e) && !e.file_type().is_dir())
+        .collect();
+    entries.sort_by(|a, b| a.path().cmp(b.path()));
+
+    for entry in entries {
+        let path = entry.path();
+        let relative = match path.strip_prefix(source) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        if pins.is_pinned(path) {
+            plan.push(FlattenMove {
+                from: path.to_string_lossy().to_string(),
+                to: path.to_string_lossy().to_string(),
+                action: "kept".to_string(),
+            });
+            continue;
+        }
+
+        let target_folder = relative
+            .components()
+            .take(options.target_depth)
+            .fold(source.clone(), |acc, c| acc.join(c));
+        let file_name = match path.file_name() {
+            Some(name) => name,
+            None => continue,
+        };
+
+        let mut dest = target_folder.join(file_name);
+        let mut action = "move";
+
+        if dest.exists() || planned.contains(&dest) {
+            // Only an existing file can be replaced. A folder with the same name (possibly
+            // the one holding this file) is kept and the file is renamed instead.
+            let strategy = match options.handle_duplicates.as_str() {
+                "overwrite" if dest.is_dir() => "rename",
+                other => other,
+            };
+            match strategy {
+                "rename" => {
+                    dest = get_unique_planned_path(&dest, &planned);
+                    action = "rename";
+                }
+                "overwrite" if dest.is_file() && !planned.contains(&dest) => action = "overwrite",
+                _ => action = "skip",
+            }
+        }
+
+        if action != "skip" {
+            planned.insert(dest.clone());
+        }
+
+        plan.push(FlattenMove {
+            from: path.to_string_lossy().to_string(),
+            to: dest.to_string_lossy().to_string(),
+            action: action.to_string(),
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Like get_unique_path, but also avoids destinations already claimed by the plan
+fn get_unique_planned_path(path: &Path, planned: &HashSet<PathBuf>) -> PathBuf {
+    let stem = path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("file");
+    let extension = path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    let parent = path.parent().unwrap_or(path);
+
+    let mut counter = 1;
+    loop {
+        let new_name = if extension.is_empty() {
+            format!("{} ({})", stem, counter)
+        } else {
+            format!("{} ({}).{}", stem, counter, extension)
+        };
+        let new_path = parent.join(new_name);
+        if !new_path.exists() && !planned.contains(&new_path) {
+            return new_path;
+        }
+        counter += 1;
+    }
+}
+
 // Helper functions
 fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
@@ -292,23 +780,7 @@ fn get_modified_time(metadata: &Option<fs::Metadata>) -> String {
         .unwrap_or_else(|| "Unknown".to_string())
 }
 
-fn get_date_folder(metadata: &Option<fs::Metadata>, format: &str) -> String {
-    metadata
-        .as_ref()
-        .and_then(|m| m.modified().ok())
-        .map(|t| {
-            let datetime: chrono::DateTime<chrono::Local> = t.into();
-            match format {
-                "YYYY-MM" => datetime.format("%Y-%m").to_string(),
-                "YYYY/MM" => datetime.format("%Y/%m").to_string(),
-                "YYYY" => datetime.format("%Y").to_string(),
-                _ => datetime.format("%Y-%m-%d").to_string(),
-            }
-        })
-        .unwrap_or_else(|| "Unknown".to_string())
-}
-
-fn get_unique_path(path: &PathBuf) -> PathBuf {
+pub(crate) fn get_unique_path(path: &PathBuf) -> PathBuf {
     let stem = path.file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("file");
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::database::{self, DbPath};
+use crate::services::filename::to_nfc;
+use crate::services::hasher::hash_file;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedFile {
+    pub id: Option<i64>,
+    pub pattern: String,
+    pub pin_type: String, // "path", "glob", "hash"
+    pub file_size: Option<u64>,
+    pub label: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Matches files against the pin list. Pinned files are never moved by
+/// organize, rule or watcher-triggered operations.
+pub struct PinMatcher {
+    paths: HashSet<PathBuf>,
+    globs: Vec<glob::Pattern>,
+    hashes: Vec<(Option<u64>, String)>,
+}
+
+impl PinMatcher {
+    pub fn new(pins: &[PinnedFile]) -> Self {
+        let mut paths = HashSet::new();
+        let mut globs = Vec::new();
+        let mut hashes = Vec::new();
+
+        for pin in pins {
+            match pin.pin_type.as_str() {
+                "path" => {
+                    paths.insert(PathBuf::from(to_nfc(&pin.pattern)));
+                }
+                "glob" => {
+                    if let Ok(pattern) = glob::Pattern::new(&pin.pattern) {
+                        globs.push(pattern);
+                    }
+                }
+                "hash" => hashes.push((pin.file_size, pin.pattern.clone())),
+                _ => {}
+            }
+        }
+
+        PinMatcher {
+            paths,
+            globs,
+            hashes,
+        }
+    }
+
+    /// Load the pin list from the database (an unreadable list pins nothing)
+    pub fn load(db_path: &PathBuf) -> Self {
+        let pins = database::get_pins(db_path).unwrap_or_default();
+        PinMatcher::new(&pins)
+    }
+
+    pub fn is_pinned(&self, path: &Path) -> bool {
+        // Paths are kept NFC so a pin matches both spellings of a Hangul name
+        if self.paths.contains(&PathBuf::from(to_nfc(&path.to_string_lossy()))) {
+            return true;
+        }
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if self
+            .globs
+            .iter()
+            .any(|g| g.matches_path(path) || g.matches(file_name))
+        {
+            return true;
+        }
+
+        if self.hashes.is_empty() {
+            return false;
+        }
+
+        // Only hash files whose size matches a hash pin
+        let size = match fs::metadata(path) {
+            Ok(m) => m.len(),
+            Err(_) => return false,
+        };
+        let candidates: Vec<&String> = self
+            .hashes
+            .iter()
+            .filter(|(pin_size, _)| pin_size.map_or(true, |s| s == size))
+            .map(|(_, hash)| hash)
+            .collect();
+
+        if candidates.is_empty() {
+            return false;
+        }
+
+        match hash_file(path) {
+            Ok(hash) => candidates.iter().any(|h| **h == hash),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Get all pinned files
+#[tauri::command]
+pub fn get_pins(db_path: State<DbPath>) -> Result<Vec<PinnedFile>, String> {
+    database::get_pins(&db_path.0)
+}
+
+/// Pin a file by exact path, glob pattern or content hash
+#[tauri::command]
+pub fn add_pin(pattern: String, pin_type: String, db_path: State<DbPath>) -> Result<PinnedFile, String> {
+    let (stored_pattern, file_size, label) = match pin_type.as_str() {
+        "path" => {
+            let label = Path::new(&pattern)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string());
+            (pattern, None, label)
+        }
+        "glob" => {
+            glob::Pattern::new(&pattern).map_err(|e| format!("잘못된 패턴입니다: {}", e))?;
+            (pattern.clone(), None, Some(pattern))
+        }
+        "hash" => {
+            // The pattern is a file path; pin its current content so renames survive
+            let path = PathBuf::from(&pattern);
+            let size = fs::metadata(&path).map_err(|e| e.to_string())?.len();
+            let hash = hash_file(&path).map_err(|e| e.to_string())?;
+            let label = path.file_name().map(|n| n.to_string_lossy().to_string());
+            (hash, Some(size), label)
+        }
+        _ => return Err(format!("Unknown pin type: {}", pin_type)),
+    };
+
+    let pin = PinnedFile {
+        id: None,
+        pattern: stored_pattern,
+        pin_type,
+        file_size,
+        label,
+        created_at: None,
+    };
+
+    let id = database::add_pin(&db_path.0, &pin)?;
+
+    Ok(PinnedFile { id: Some(id), ..pin })
+}
+
+/// Remove a pin
+#[tauri::command]
+pub fn remove_pin(id: i64, db_path: State<DbPath>) -> Result<(), String> {
+    database::remove_pin(&db_path.0, id)
+}
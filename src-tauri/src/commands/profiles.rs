@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::commands::jobs::JobState;
+use crate::commands::rules::{
+    get_default_rules_internal, get_rules_internal, move_previews, preview_destinations,
+    preview_unified_with, DefaultRule, MoveOutcome, Rule, UnifiedOrganizeResult, UnifiedPreview,
+};
+use crate::database::{self, DbPath};
+
+/// A named set of source folders organized together with their own rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeProfile {
+    pub id: Option<i64>,
+    pub name: String,
+    pub sources: Vec<String>,
+    /// Root for default-rule destinations (each source folder itself when None)
+    pub destination_root: Option<String>,
+    /// Profile-specific default rules (the global default rules when empty)
+    #[serde(default)]
+    pub default_rules: Vec<DefaultRule>,
+    #[serde(default)]
+    pub options: ProfileOptions,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileOptions {
+    pub include_custom_rules: bool,
+    pub handle_duplicates: String, // "overwrite", "rename", "skip"
+}
+
+impl Default for ProfileOptions {
+    fn default() -> Self {
+        ProfileOptions {
+            include_custom_rules: true,
+            handle_duplicates: "rename".to_string(),
+        }
+    }
+}
+
+/// Get all organize profiles
+#[tauri::command]
+pub fn get_profiles(db_path: State<DbPath>) -> Result<Vec<OrganizeProfile>, String> {
+    database::get_profiles(&db_path.0)
+}
+
+/// Create or update an organize profile
+#[tauri::command]
+pub fn save_profile(profile: OrganizeProfile, db_path: State<DbPath>) -> Result<OrganizeProfile, String> {
+    if profile.name.trim().is_empty() {
+        return Err("프로필 이름을 입력하세요".to_string());
+    }
+    if profile.sources.is_empty() {
+        return Err("소스 폴더를 하나 이상 지정하세요".to_string());
+    }
+
+    let id = database::save_profile(&db_path.0, &profile)?;
+    database::get_profile(&db_path.0, id)
+}
+
+/// Delete an organize profile
+#[tauri::command]
+pub fn delete_profile(id: i64, db_path: State<DbPath>) -> Result<(), String> {
+    database::delete_profile(&db_path.0, id)
+}
+
+/// Preview a profile across all of its source folders
+#[tauri::command]
+pub fn preview_profile(id: i64, db_path: State<DbPath>) -> Result<Vec<UnifiedPreview>, String> {
+    let profile = database::get_profile(&db_path.0, id)?;
+    let (custom_rules, default_rules) = profile_rules(&db_path.0, &profile)?;
+    let destination_root = profile.destination_root.as_deref().map(expand_home);
+
+    let mut previews = Vec::new();
+    for source in &profile.sources {
+        previews.extend(preview_unified_with(
+            &db_path.0,
+            &expand_home(source).to_string_lossy(),
+            &custom_rules,
+            &default_rules,
+            destination_root.as_deref(),
+        )?);
+    }
+
+    Ok(previews)
+}
+
+/// Organize every source folder of a profile, recorded as one history entry
+#[tauri::command]
+pub fn execute_profile(
+    id: i64,
+    excluded_destinations: Option<Vec<String>>,
+    db_path: State<DbPath>,
+    jobs: State<JobState>,
+) -> Result<UnifiedOrganizeResult, String> {
+    let db_path = db_path.0.clone();
+    let profile = database::get_profile(&db_path, id)?;
+    let (custom_rules, default_rules) = profile_rules(&db_path, &profile)?;
+    let destination_root = profile.destination_root.as_deref().map(expand_home);
+    let excluded = excluded_destinations.unwrap_or_default();
+
+    let mut total = MoveOutcome::default();
+    // Every source and destination is locked before the first file moves
+    let mut paths: Vec<PathBuf> = destination_root.iter().cloned().collect();
+    let mut batches = Vec::new();
+    for source in &profile.sources {
+        let source_path = expand_home(source);
+        let previews = match preview_unified_with(
+            &db_path,
+            &source_path.to_string_lossy(),
+            &custom_rules,
+            &default_rules,
+            destination_root.as_deref(),
+        ) {
+            Ok(previews) => previews,
+            Err(e) => {
+                // A missing source folder should not stop the other sources
+                total.errors.push(format!("{}: {}", source, e));
+                continue;
+            }
+        };
+        paths.push(source_path);
+        paths.extend(preview_destinations(&previews));
+        batches.push(previews);
+    }
+
+    jobs.0.run_locked(paths, |_| {
+        for previews in batches {
+            let outcome = move_previews(
+                &db_path,
+                previews,
+                &excluded,
+                &profile.options.handle_duplicates,
+            );
+            total.files_moved += outcome.files_moved;
+            total.files_skipped += outcome.files_skipped;
+            total.files_deferred += outcome.files_deferred;
+            total.deferred.extend(outcome.deferred);
+            total.errors.extend(outcome.errors);
+            total.move_details.extend(outcome.move_details);
+            total.trashed.extend(outcome.trashed);
+            total.quarantined.extend(outcome.quarantined);
+        }
+        Ok(())
+    })?;
+
+    // Record history; a run that moved nothing leaves no entry
+    let history_id = if total.move_details.is_empty()
+        && total.trashed.is_empty()
+        && total.quarantined.is_empty()
+    {
+        -1
+    } else {
+        let details_json = serde_json::json!({
+            "action": "organize",
+            "moves": total.move_details,
+            "trashed": total.trashed,
+            "quarantined": total.quarantined,
+        });
+        database::add_history(
+            &db_path,
+            "organize",
+            &format!(
+                "프로필 정리 ({}): {}개 폴더, {}개 파일 이동",
+                profile.name,
+                profile.sources.len(),
+                total.files_moved
+            ),
+            &details_json.to_string(),
+        )
+        .unwrap_or(-1)
+    };
+
+    Ok(UnifiedOrganizeResult {
+        success: total.errors.is_empty(),
+        files_moved: total.files_moved,
+        files_skipped: total.files_skipped,
+        files_deferred: total.files_deferred,
+        deferred: total.deferred,
+        errors: total.errors,
+        history_id,
+    })
+}
+
+/// Enabled custom and default rules used by a profile
+fn profile_rules(
+    db_path: &PathBuf,
+    profile: &OrganizeProfile,
+) -> Result<(Vec<Rule>, Vec<DefaultRule>), String> {
+    let custom_rules: Vec<Rule> = if profile.options.include_custom_rules {
+        get_rules_internal(db_path)?
+            .into_iter()
+            .filter(|r| r.enabled)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let default_rules = if profile.default_rules.is_empty() {
+        get_default_rules_internal(db_path)?
+    } else {
+        profile.default_rules.clone()
+    };
+
+    let default_rules = default_rules
+        .into_iter()
+        .filter(|r| r.enabled)
+        .map(|r| DefaultRule {
+            destination: expand_home(&r.destination).to_string_lossy().to_string(),
+            ..r
+        })
+        .collect();
+
+    Ok((custom_rules, default_rules))
+}
+
+/// Expand a leading "~" to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix('~') {
+        if let Some(dirs) = directories::BaseDirs::new() {
+            return dirs.home_dir().join(rest.trim_start_matches(['/', '\\']));
+        }
+    }
+    PathBuf::from(path)
+}
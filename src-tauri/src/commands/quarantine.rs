@@ -0,0 +1,247 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::database::{self, DbPath};
+use crate::services::{mover, trash_bin};
+
+/// Days a quarantined file is kept when the retention setting is missing
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedFile {
+    pub id: Option<i64>,
+    pub original_path: String,
+    pub stored_path: String,
+    /// Why the file was removed, e.g. the rule name or duplicate cleanup
+    pub reason: String,
+    pub size: u64,
+    pub quarantined_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantineResult {
+    pub processed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Quarantine settings: whether removed files go to the app's quarantine folder
+/// instead of the OS trash, where that folder is and how long files stay there
+pub struct QuarantinePolicy {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    /// 0 keeps files until they are purged by hand
+    pub retention_days: u32,
+}
+
+impl QuarantinePolicy {
+    /// Build the policy from the "use_quarantine", "quarantine_path" and
+    /// "quarantine_retention_days" settings
+    pub fn load(db_path: &PathBuf) -> Self {
+        let read = |key: &str| database::get_setting(db_path, key).ok().flatten();
+
+        QuarantinePolicy {
+            enabled: read("use_quarantine").map(|v| v == "true").unwrap_or(false),
+            dir: read("quarantine_path")
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| default_quarantine_dir(db_path)),
+            retention_days: read("quarantine_retention_days")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_RETENTION_DAYS),
+        }
+    }
+}
+
+/// The quarantine folder lives next to the database in the app data folder by default
+pub fn default_quarantine_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("quarantine")
+}
+
+/// Where a file removed by a rule or duplicate cleanup went
+pub enum Removal {
+    /// Sent to the OS trash at this time (unix seconds)
+    Trashed(i64),
+    /// Moved into the quarantine, with this index id
+    Quarantined(i64),
+}
+
+/// Remove a file into the quarantine when it is enabled, otherwise into the OS trash
+pub(crate) fn remove_file(db_path: &PathBuf, path: &Path, reason: &str) -> Result<Removal, String> {
+    let policy = QuarantinePolicy::load(db_path);
+    if policy.enabled {
+        quarantine_file(db_path, &policy.dir, path, reason).map(Removal::Quarantined)
+    } else {
+        trash_bin::move_to_trash(path).map(Removal::Trashed)
+    }
+}
+
+/// Move a file into the quarantine folder and index it
+pub(crate) fn quarantine_file(
+    db_path: &PathBuf,
+    quarantine_dir: &Path,
+    path: &Path,
+    reason: &str,
+) -> Result<i64, String> {
+    let file_name = path.file_name().ok_or("파일 이름을 알 수 없습니다")?;
+    let size = fs::symlink_metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    // Each file gets its own folder, so names from different places never collide
+    let item_dir = unique_item_dir(quarantine_dir);
+    fs::create_dir_all(&item_dir).map_err(|e| format!("격리 폴더를 만들 수 없습니다: {}", e))?;
+    let stored_path = item_dir.join(file_name);
+
+    if let Err(e) = mover::move_file(path, &stored_path) {
+        let _ = fs::remove_dir(&item_dir);
+        return Err(format!("격리 실패: {}", e));
+    }
+
+    database::add_quarantined(
+        db_path,
+        &QuarantinedFile {
+            id: None,
+            original_path: path.to_string_lossy().to_string(),
+            stored_path: stored_path.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+            size,
+            quarantined_at: None,
+        },
+    )
+}
+
+fn unique_item_dir(quarantine_dir: &Path) -> PathBuf {
+    let stamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let mut dir = quarantine_dir.join(&stamp);
+    let mut counter = 1;
+    while dir.exists() {
+        dir = quarantine_dir.join(format!("{}_{}", stamp, counter));
+        counter += 1;
+    }
+    dir
+}
+
+/// Move quarantined files back to where they came from; returns one message per failure
+pub(crate) fn restore_quarantined_files(db_path: &PathBuf, ids: &[i64]) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for &id in ids {
+        let result = database::get_quarantined_file(db_path, id).and_then(|item| {
+            restore_item(&item)?;
+            database::delete_quarantined(db_path, id)
+        });
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    }
+
+    errors
+}
+
+fn restore_item(item: &QuarantinedFile) -> Result<(), String> {
+    let original_path = PathBuf::from(&item.original_path);
+    let stored_path = PathBuf::from(&item.stored_path);
+
+    if fs::symlink_metadata(&stored_path).is_err() {
+        return Err(format!("격리된 파일이 없습니다: {}", item.stored_path));
+    }
+    if fs::symlink_metadata(&original_path).is_ok() {
+        return Err(format!("원래 위치에 같은 이름의 파일이 있습니다: {}", item.original_path));
+    }
+
+    if let Some(parent) = original_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    mover::move_file(&stored_path, &original_path)
+        .map_err(|e| format!("복원 실패 {}: {}", item.original_path, e))?;
+
+    if let Some(item_dir) = stored_path.parent() {
+        let _ = fs::remove_dir(item_dir);
+    }
+    Ok(())
+}
+
+/// Delete a quarantined file for good and drop it from the index
+fn purge_item(db_path: &PathBuf, item: &QuarantinedFile) -> Result<(), String> {
+    let stored_path = PathBuf::from(&item.stored_path);
+
+    if let Ok(metadata) = fs::symlink_metadata(&stored_path) {
+        if metadata.is_dir() {
+            fs::remove_dir_all(&stored_path).map_err(|e| e.to_string())?;
+        } else {
+            fs::remove_file(&stored_path).map_err(|e| e.to_string())?;
+        }
+    }
+    if let Some(item_dir) = stored_path.parent() {
+        let _ = fs::remove_dir(item_dir);
+    }
+
+    database::delete_quarantined(db_path, item.id.unwrap_or_default())
+}
+
+/// Purge files kept longer than the retention period (run at startup)
+pub(crate) fn purge_expired(db_path: &PathBuf) -> Result<QuarantineResult, String> {
+    let policy = QuarantinePolicy::load(db_path);
+    let mut result = QuarantineResult {
+        processed: 0,
+        errors: Vec::new(),
+    };
+
+    if policy.retention_days == 0 {
+        return Ok(result);
+    }
+
+    for item in database::get_expired_quarantine(db_path, policy.retention_days)? {
+        match purge_item(db_path, &item) {
+            Ok(()) => result.processed += 1,
+            Err(e) => result.errors.push(format!("{}: {}", item.original_path, e)),
+        }
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn get_quarantined_files(db_path: State<DbPath>) -> Result<Vec<QuarantinedFile>, String> {
+    database::get_quarantined_files(&db_path.0)
+}
+
+#[tauri::command]
+pub fn restore_quarantined(ids: Vec<i64>, db_path: State<DbPath>) -> Result<QuarantineResult, String> {
+    let errors = restore_quarantined_files(&db_path.0, &ids);
+
+    Ok(QuarantineResult {
+        processed: ids.len() - errors.len(),
+        errors,
+    })
+}
+
+#[tauri::command]
+pub fn purge_quarantined(ids: Vec<i64>, db_path: State<DbPath>) -> Result<QuarantineResult, String> {
+    let mut result = QuarantineResult {
+        processed: 0,
+        errors: Vec::new(),
+    };
+
+    for id in ids {
+        match database::get_quarantined_file(&db_path.0, id)
+            .and_then(|item| purge_item(&db_path.0, &item))
+        {
+            Ok(()) => result.processed += 1,
+            Err(e) => result.errors.push(e),
+        }
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn purge_expired_quarantine(db_path: State<DbPath>) -> Result<QuarantineResult, String> {
+    purge_expired(&db_path.0)
+}
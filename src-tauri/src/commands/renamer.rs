@@ -1,15 +1,30 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
-use crate::database::DbPath;
+use crate::commands::rules::{evaluate_conditions, Condition};
+use crate::commands::scanner::{get_file_info_internal, FileInfo};
+use crate::database::{self, DbPath};
+use crate::services::batch_rename::{self, RenameOp};
+use crate::services::classifier::{classify_extension, get_category_korean_name};
+use crate::services::date_source::{resolve_file_date, DateSource};
+use crate::services::extension::{corrected_extension, unify_alias};
+use crate::services::filename::{sanitize_filename, to_nfc, to_nfd};
+use crate::services::link_policy::WalkPolicy;
+use crate::services::metadata::MetadataCache;
+use crate::services::rename_template::{
+    is_valid_date_format, title_case, RenameTemplate, TemplateContext,
+};
+use crate::services::sequence::{
+    number_files, CounterScope, SequenceNumber, SequenceOptions, SequenceSort,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RenameRule {
-    pub rule_type: String, // "findReplace", "prefix", "suffix", "sequence", "date", "case", "regex"
+    pub rule_type: String, // "findReplace", "prefix", "suffix", "sequence", "date", "case", "regex", "template", "normalize", "sanitize", "extension"
     pub find_text: Option<String>,
     pub replace_text: Option<String>,
     pub prefix: Option<String>,
@@ -17,10 +32,35 @@ pub struct RenameRule {
     pub start_number: Option<i32>,
     pub digit_count: Option<i32>,
     pub date_format: Option<String>,
-    pub date_source: Option<String>, // "created" or "modified"
+    pub date_source: Option<String>, // "exif", "filename", "created" or "modified"
     pub case_type: Option<String>,   // "upper", "lower", "title"
     pub regex_pattern: Option<String>,
     pub regex_replace: Option<String>,
+    /// Full-name template for "template" rules, e.g. "{date:%Y-%m-%d}_{name:slug}_{n:03}{ext:lower}"
+    pub template: Option<String>,
+    /// Unicode form for "normalize" rules: "nfc" (default) or "nfd"
+    pub normalize_form: Option<String>,
+    /// Numbering order: "input" (default), "name", "date", "size" or "custom"
+    pub sort_by: Option<String>,
+    #[serde(default)]
+    pub sort_descending: bool,
+    /// File paths in the desired order for "custom" sorting
+    pub custom_order: Option<Vec<String>>,
+    /// Files sharing a counter: "global" (default), "folder" or "extension"
+    pub counter_scope: Option<String>,
+    pub step: Option<i32>,
+    pub offset: Option<i32>,
+    /// Sequence name pattern with {name}, {n} and {total}, e.g. "{name} ({n} of {total})"
+    pub sequence_format: Option<String>,
+    /// For "extension" rules: "lower" (default), "upper", "unify", "sniff" or "set"
+    pub extension_action: Option<String>,
+    /// Extension used by the "set" action
+    pub new_extension: Option<String>,
+    /// Only files matching these conditions are changed by this rule (all files when empty)
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// "AND" (default) or "OR"
+    pub condition_logic: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +71,8 @@ pub struct RenamePreview {
     pub new_name: String,
     pub has_conflict: bool,
     pub conflict_message: Option<String>,
+    /// Set when an extension change alters how the file is classified
+    pub warning: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,24 +81,108 @@ pub struct RenameResult {
     pub success: bool,
     pub renamed_count: usize,
     pub failed_count: usize,
+    /// Rename cycles (e.g. swapped names) completed through temporary names
+    pub cycles_resolved: usize,
     pub errors: Vec<String>,
 }
 
+/// A named, ordered list of rename rules (including templates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamePreset {
+    pub id: Option<i64>,
+    pub name: String,
+    pub rules: Vec<RenameRule>,
+    pub created_at: Option<String>,
+}
+
 /// Preview rename operation
 #[tauri::command]
 pub fn preview_rename(
+    db_state: State<DbPath>,
+    file_paths: Vec<String>,
+    rules: Vec<RenameRule>,
+) -> Result<Vec<RenamePreview>, String> {
+    preview_rename_internal(&db_state.0, file_paths, rules)
+}
+
+fn preview_rename_internal(
+    db_path: &PathBuf,
     file_paths: Vec<String>,
     rules: Vec<RenameRule>,
 ) -> Result<Vec<RenamePreview>, String> {
     let mut previews: Vec<RenamePreview> = Vec::new();
-    let mut new_names: Vec<String> = Vec::new();
-    let mut sequence_counter = rules
+    let mut new_paths: Vec<PathBuf> = Vec::new();
+    // Parse templates and date formats up front so a bad one fails the whole preview
+    let templates: Vec<Option<RenameTemplate>> = rules
+        .iter()
+        .map(|r| match r.rule_type.as_str() {
+            "template" => RenameTemplate::parse(r.template.as_deref().unwrap_or("")).map(Some),
+            "date" => match r.date_format.as_deref() {
+                Some(format) if !is_valid_date_format(format) => {
+                    Err(format!("잘못된 날짜 형식 '{}'", format))
+                }
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let paths: Vec<PathBuf> = file_paths.iter().map(PathBuf::from).collect();
+
+    // Rule filters look at the files as they are before renaming
+    let file_infos: Vec<Option<FileInfo>> = if rules.iter().any(|r| !r.conditions.is_empty()) {
+        let policy = WalkPolicy::load(db_path);
+        paths
+            .iter()
+            .map(|p| get_file_info_internal(p, &policy).ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let applies: Vec<Vec<bool>> = rules
+        .iter()
+        .map(|rule| {
+            (0..paths.len())
+                .map(|i| {
+                    rule.conditions.is_empty()
+                        || file_infos[i].as_ref().is_some_and(|info| {
+                            evaluate_conditions(
+                                info,
+                                &rule.conditions,
+                                rule.condition_logic.as_deref().unwrap_or("AND"),
+                            )
+                        })
+                })
+                .collect()
+        })
+        .collect();
+
+    // Numbers for sequence rules and counting templates, assigned in each rule's sort
+    // order among the files the rule applies to
+    let numbering: Vec<Vec<Option<SequenceNumber>>> = rules
         .iter()
-        .find(|r| r.rule_type == "sequence")
-        .and_then(|r| r.start_number)
-        .unwrap_or(1);
+        .zip(&templates)
+        .zip(&applies)
+        .map(|((rule, template), applies)| {
+            let mut numbers = vec![None; paths.len()];
+            let counts = rule.rule_type == "sequence"
+                || template.as_ref().is_some_and(|t| t.uses_counter());
+            if counts {
+                let indices: Vec<usize> = (0..paths.len()).filter(|&i| applies[i]).collect();
+                let subset: Vec<PathBuf> = indices.iter().map(|&i| paths[i].clone()).collect();
+                let subset_numbers = number_files(&subset, &sequence_options(rule));
+                for (i, number) in indices.into_iter().zip(subset_numbers) {
+                    numbers[i] = Some(number);
+                }
+            }
+            numbers
+        })
+        .collect();
+    // Each file's embedded metadata is read at most once per preview
+    let mut metadata_cache = MetadataCache::new();
 
-    for file_path in &file_paths {
+    for (file_index, file_path) in file_paths.iter().enumerate() {
         let path = PathBuf::from(file_path);
         let original_name = path
             .file_name()
@@ -70,7 +196,7 @@ pub fn preview_rename(
             .and_then(|s| s.to_str())
             .unwrap_or(&original_name)
             .to_string();
-        let extension = path
+        let mut extension = path
             .extension()
             .and_then(|e| e.to_str())
             .map(|e| format!(".{}", e))
@@ -79,21 +205,84 @@ pub fn preview_rename(
         // Apply rules to get new name
         let mut new_stem = stem.clone();
 
-        for rule in &rules {
-            new_stem = apply_rule(&new_stem, rule, &path, &mut sequence_counter);
+        for (index, rule) in rules.iter().enumerate() {
+            if !applies[index][file_index] {
+                continue;
+            }
+            let sequence = numbering[index][file_index];
+            match &templates[index] {
+                Some(template) => {
+                    // A template renders the whole name, extension included; split it
+                    // back so later rules see the stem and extension separately
+                    let metadata = if template.uses_metadata() {
+                        Some(metadata_cache.get(&path))
+                    } else {
+                        None
+                    };
+                    let mut rendered = template.render(&TemplateContext {
+                        path: &path,
+                        stem: &new_stem,
+                        extension: &extension,
+                        counter: sequence.map(|s| s.number).unwrap_or(1),
+                        total: sequence.map(|s| s.total).unwrap_or(file_paths.len()),
+                        date_source: DateSource::parse(rule.date_source.as_deref().unwrap_or("modified")),
+                        metadata,
+                    });
+                    extension = match rendered.rfind('.').filter(|&i| i > 0) {
+                        Some(index) => rendered.split_off(index),
+                        None => String::new(),
+                    };
+                    new_stem = rendered;
+                }
+                // Sanitizing looks at the whole name (trailing dots, reserved names, length)
+                None if rule.rule_type == "sanitize" => {
+                    let sanitized = sanitize_filename(&format!("{}{}", new_stem, extension));
+                    match sanitized.strip_suffix(extension.as_str()) {
+                        Some(stem) if !extension.is_empty() => new_stem = stem.to_string(),
+                        _ => {
+                            new_stem = sanitized;
+                            extension = String::new();
+                        }
+                    }
+                }
+                None if rule.rule_type == "extension" => {
+                    let changed = apply_extension_rule(extension.trim_start_matches('.'), rule, &path);
+                    extension = if changed.is_empty() {
+                        String::new()
+                    } else {
+                        format!(".{}", changed)
+                    };
+                }
+                None => new_stem = apply_rule(&new_stem, rule, &path, sequence),
+            }
         }
 
         let new_name = format!("{}{}", new_stem, extension);
 
-        // Check for conflicts
-        let has_conflict = new_names.contains(&new_name);
-        let conflict_message = if has_conflict {
-            Some("이름이 중복됩니다".to_string())
-        } else {
-            None
-        };
+        // Warn when the new extension moves the file to another category
+        let old_category = classify_extension(&dotted_extension(&original_name));
+        let new_category = classify_extension(&dotted_extension(&new_name));
+        let warning = (old_category != new_category).then(|| {
+            format!(
+                "분류가 바뀝니다: {} → {}",
+                get_category_korean_name(&old_category),
+                get_category_korean_name(&new_category)
+            )
+        });
 
-        new_names.push(new_name.clone());
+        // Check for conflicts within the same folder (NFC and NFD spellings of a name
+        // are the same name). A name that is not a single path component would move
+        // the file elsewhere, so it is never renamed to.
+        let new_path = path.parent().unwrap_or(Path::new("")).join(to_nfc(&new_name));
+        let conflict_message = match invalid_name_reason(&new_name) {
+            Some(reason) => Some(reason.to_string()),
+            None if new_paths.contains(&new_path) => Some("이름이 중복됩니다".to_string()),
+            None => {
+                new_paths.push(new_path);
+                None
+            }
+        };
+        let has_conflict = conflict_message.is_some();
 
         previews.push(RenamePreview {
             original_path: file_path.clone(),
@@ -101,6 +290,7 @@ pub fn preview_rename(
             new_name,
             has_conflict,
             conflict_message,
+            warning,
         });
     }
 
@@ -114,14 +304,19 @@ pub fn execute_rename(
     file_paths: Vec<String>,
     rules: Vec<RenameRule>,
 ) -> Result<RenameResult, String> {
-    let db_path = &db_state.0;
+    execute_rename_internal(&db_state.0, file_paths, rules)
+}
 
-    let previews = preview_rename(file_paths.clone(), rules)?;
+fn execute_rename_internal(
+    db_path: &PathBuf,
+    file_paths: Vec<String>,
+    rules: Vec<RenameRule>,
+) -> Result<RenameResult, String> {
+    let previews = preview_rename_internal(db_path, file_paths.clone(), rules)?;
 
-    let mut renamed_count = 0;
     let mut failed_count = 0;
     let mut errors: Vec<String> = Vec::new();
-    let mut rename_details: Vec<(String, String)> = Vec::new();
+    let mut ops: Vec<RenameOp> = Vec::new();
 
     for preview in &previews {
         if preview.has_conflict {
@@ -135,37 +330,48 @@ pub fn execute_rename(
         }
 
         let original_path = PathBuf::from(&preview.original_path);
-        let new_path = original_path.with_file_name(&preview.new_name);
+        ops.push(RenameOp {
+            to: original_path.with_file_name(&preview.new_name),
+            from: original_path,
+        });
+    }
 
-        if new_path.exists() && preview.original_name != preview.new_name {
-            failed_count += 1;
-            errors.push(format!("File already exists: {}", preview.new_name));
-            continue;
-        }
+    // Swaps, chains and case-only renames go through temporary names
+    let outcome = batch_rename::execute_batch(&ops);
 
-        match fs::rename(&original_path, &new_path) {
-            Ok(_) => {
-                rename_details.push((
-                    preview.original_path.clone(),
-                    new_path.to_string_lossy().to_string(),
-                ));
-                renamed_count += 1;
-            }
-            Err(e) => {
-                failed_count += 1;
-                errors.push(format!("Failed to rename {}: {}", preview.original_name, e));
-            }
-        }
+    for (path, error) in &outcome.errors {
+        failed_count += 1;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        errors.push(format!("Failed to rename {}: {}", name, error));
     }
 
+    let renamed_count = outcome.renamed.len();
+    let rename_details: Vec<(String, String)> = outcome
+        .renamed
+        .iter()
+        .map(|(from, to)| {
+            (
+                from.to_string_lossy().to_string(),
+                to.to_string_lossy().to_string(),
+            )
+        })
+        .collect();
+
     // Record history
     if renamed_count > 0 {
         let details_json = serde_json::json!({
             "action": "rename",
             "files": rename_details.iter().map(|(orig, new)| {
+                // Size and mtime let undo skip files that were edited after the rename
+                let stamp = batch_rename::file_stamp(Path::new(new));
                 serde_json::json!({
                     "original_path": orig,
-                    "new_path": new
+                    "new_path": new,
+                    "size": stamp.map(|(size, _)| size),
+                    "modified": stamp.map(|(_, modified)| modified)
                 })
             }).collect::<Vec<_>>()
         });
@@ -182,11 +388,78 @@ pub fn execute_rename(
         success: failed_count == 0,
         renamed_count,
         failed_count,
+        cycles_resolved: outcome.cycles,
         errors,
     })
 }
 
-fn apply_rule(stem: &str, rule: &RenameRule, path: &PathBuf, sequence: &mut i32) -> String {
+/// New extension (without the dot) for an "extension" rule
+fn apply_extension_rule(extension: &str, rule: &RenameRule, path: &Path) -> String {
+    match rule.extension_action.as_deref().unwrap_or("lower") {
+        "lower" => extension.to_lowercase(),
+        "upper" => extension.to_uppercase(),
+        "unify" => unify_alias(extension),
+        // Add a missing extension or replace a wrong one, when the content is recognized
+        "sniff" => corrected_extension(path, extension)
+            .map(|e| e.to_string())
+            .unwrap_or_else(|| extension.to_string()),
+        "set" => rule
+            .new_extension
+            .as_deref()
+            .unwrap_or(extension)
+            .trim_start_matches('.')
+            .to_string(),
+        _ => extension.to_string(),
+    }
+}
+
+/// Why a rendered name cannot be used as a file name, if it cannot
+fn invalid_name_reason(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("이름이 비어 있습니다")
+    } else if name == "." || name == ".." {
+        Some("'.' 또는 '..'는 이름으로 쓸 수 없습니다")
+    } else if name.contains(['/', '\\', '\0']) {
+        Some("이름에 '/', '\\' 또는 NUL 문자를 쓸 수 없습니다")
+    } else {
+        None
+    }
+}
+
+/// ".ext" of a file name, empty when it has none
+fn dotted_extension(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+/// Numbering options of a "sequence" or counting "template" rule
+fn sequence_options(rule: &RenameRule) -> SequenceOptions {
+    let sort = match rule.sort_by.as_deref().unwrap_or("input") {
+        "name" => SequenceSort::Name,
+        "date" => SequenceSort::Date(DateSource::parse(rule.date_source.as_deref().unwrap_or("exif"))),
+        "size" => SequenceSort::Size,
+        "custom" => SequenceSort::Custom(rule.custom_order.clone().unwrap_or_default()),
+        _ => SequenceSort::Input,
+    };
+
+    SequenceOptions {
+        sort,
+        descending: rule.sort_descending,
+        scope: CounterScope::parse(rule.counter_scope.as_deref().unwrap_or("global")),
+        start: rule.start_number.unwrap_or(1) as i64,
+        step: rule.step.unwrap_or(1) as i64,
+        offset: rule.offset.unwrap_or(0) as i64,
+    }
+}
+
+fn apply_rule(
+    stem: &str,
+    rule: &RenameRule,
+    path: &PathBuf,
+    sequence: Option<SequenceNumber>,
+) -> String {
     match rule.rule_type.as_str() {
         "findReplace" => {
             let find = rule.find_text.as_deref().unwrap_or("");
@@ -206,28 +479,26 @@ fn apply_rule(stem: &str, rule: &RenameRule, path: &PathBuf, sequence: &mut i32)
             format!("{}{}", stem, suffix)
         }
         "sequence" => {
+            let Some(sequence) = sequence else {
+                return stem.to_string();
+            };
             let digit_count = rule.digit_count.unwrap_or(3) as usize;
-            let seq_str = format!("{:0>width$}", sequence, width = digit_count);
-            *sequence += 1;
-            format!("{}_{}", stem, seq_str)
+            let seq_str = format!("{:0width$}", sequence.number, width = digit_count);
+            match rule.sequence_format.as_deref() {
+                Some(format) => format
+                    .replace("{name}", stem)
+                    .replace("{n}", &seq_str)
+                    .replace("{total}", &sequence.total.to_string()),
+                None => format!("{}_{}", stem, seq_str),
+            }
         }
         "date" => {
             let format = rule.date_format.as_deref().unwrap_or("%Y%m%d");
-            let date_source = rule.date_source.as_deref().unwrap_or("modified");
+            let date_source = DateSource::parse(rule.date_source.as_deref().unwrap_or("modified"));
+            let metadata = fs::metadata(path).ok();
 
-            let datetime = if date_source == "created" {
-                fs::metadata(path)
-                    .and_then(|m| m.created())
-                    .ok()
-            } else {
-                fs::metadata(path)
-                    .and_then(|m| m.modified())
-                    .ok()
-            };
-
-            if let Some(time) = datetime {
-                let dt: chrono::DateTime<chrono::Local> = time.into();
-                let date_str = dt.format(format).to_string();
+            if let Some(resolved) = resolve_file_date(path, metadata.as_ref(), date_source) {
+                let date_str = resolved.datetime.format(format).to_string();
                 format!("{}_{}", stem, date_str)
             } else {
                 stem.to_string()
@@ -238,25 +509,14 @@ fn apply_rule(stem: &str, rule: &RenameRule, path: &PathBuf, sequence: &mut i32)
             match case_type {
                 "upper" => stem.to_uppercase(),
                 "lower" => stem.to_lowercase(),
-                "title" => {
-                    // Simple title case
-                    stem.split_whitespace()
-                        .map(|word| {
-                            let mut chars = word.chars();
-                            match chars.next() {
-                                Some(first) => {
-                                    first.to_uppercase().collect::<String>()
-                                        + chars.as_str().to_lowercase().as_str()
-                                }
-                                None => String::new(),
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                }
+                "title" => title_case(stem),
                 _ => stem.to_string(),
             }
         }
+        "normalize" => match rule.normalize_form.as_deref().unwrap_or("nfc") {
+            "nfd" => to_nfd(stem),
+            _ => to_nfc(stem),
+        },
         "regex" => {
             let pattern = rule.regex_pattern.as_deref().unwrap_or("");
             let replace = rule.regex_replace.as_deref().unwrap_or("");
@@ -270,3 +530,294 @@ fn apply_rule(stem: &str, rule: &RenameRule, path: &PathBuf, sequence: &mut i32)
         _ => stem.to_string(),
     }
 }
+
+/// Get all rename presets
+#[tauri::command]
+pub fn get_rename_presets(db_path: State<DbPath>) -> Result<Vec<RenamePreset>, String> {
+    database::get_rename_presets(&db_path.0)
+}
+
+/// Create or update a rename preset
+#[tauri::command]
+pub fn save_rename_preset(preset: RenamePreset, db_path: State<DbPath>) -> Result<RenamePreset, String> {
+    if preset.name.trim().is_empty() {
+        return Err("프리셋 이름을 입력하세요".to_string());
+    }
+    if preset.rules.is_empty() {
+        return Err("규칙을 하나 이상 추가하세요".to_string());
+    }
+
+    let id = database::save_rename_preset(&db_path.0, &preset)?;
+    database::get_rename_preset(&db_path.0, id)
+}
+
+/// Delete a rename preset
+#[tauri::command]
+pub fn delete_rename_preset(id: i64, db_path: State<DbPath>) -> Result<(), String> {
+    database::delete_rename_preset(&db_path.0, id)
+}
+
+/// Preview a rename with a saved preset's rules
+#[tauri::command]
+pub fn preview_rename_preset(
+    id: i64,
+    file_paths: Vec<String>,
+    db_path: State<DbPath>,
+) -> Result<Vec<RenamePreview>, String> {
+    let preset = database::get_rename_preset(&db_path.0, id)?;
+    preview_rename_internal(&db_path.0, file_paths, preset.rules)
+}
+
+/// Rename files with a saved preset's rules
+#[tauri::command]
+pub fn execute_rename_preset(
+    id: i64,
+    file_paths: Vec<String>,
+    db_path: State<DbPath>,
+) -> Result<RenameResult, String> {
+    let preset = database::get_rename_preset(&db_path.0, id)?;
+    execute_rename_internal(&db_path.0, file_paths, preset.rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn rule(value: serde_json::Value) -> RenameRule {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn new_names(previews: &[RenamePreview]) -> Vec<&str> {
+        previews.iter().map(|p| p.new_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rules_after_template_keep_extension() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("x.jpg");
+        fs::write(&path, b"data").unwrap();
+
+        let rules = vec![
+            rule(serde_json::json!({ "ruleType": "template", "template": "{name}{ext}" })),
+            rule(serde_json::json!({ "ruleType": "suffix", "suffix": "_x" })),
+        ];
+        let file_paths = vec![path.to_string_lossy().to_string()];
+        let db_path = dir.path().join("test.db");
+        let previews = preview_rename_internal(&db_path, file_paths, rules).unwrap();
+
+        assert_eq!(new_names(&previews), vec!["x_x.jpg"]);
+    }
+
+    #[test]
+    fn test_same_name_in_different_folders_is_not_a_conflict() {
+        let dir = tempdir().unwrap();
+        let mut file_paths = Vec::new();
+        for (folder, name) in [("A", "x.jpg"), ("B", "y.jpg"), ("B", "z.jpg")] {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+            let path = dir.path().join(folder).join(name);
+            fs::write(&path, b"data").unwrap();
+            file_paths.push(path.to_string_lossy().to_string());
+        }
+
+        let rules = vec![rule(serde_json::json!({
+            "ruleType": "template",
+            "template": "{n:03}{ext}",
+            "counterScope": "folder",
+        }))];
+        let db_path = dir.path().join("test.db");
+        let previews = preview_rename_internal(&db_path, file_paths, rules).unwrap();
+
+        assert_eq!(new_names(&previews), vec!["001.jpg", "001.jpg", "002.jpg"]);
+        assert!(previews.iter().all(|p| !p.has_conflict));
+    }
+
+    #[test]
+    fn test_negative_step_pads_after_the_sign() {
+        let dir = tempdir().unwrap();
+        let mut file_paths = Vec::new();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            let path = dir.path().join(name);
+            fs::write(&path, b"data").unwrap();
+            file_paths.push(path.to_string_lossy().to_string());
+        }
+        let db_path = dir.path().join("test.db");
+
+        let sequence = vec![rule(serde_json::json!({
+            "ruleType": "sequence",
+            "startNumber": 1,
+            "step": -1,
+            "digitCount": 3,
+        }))];
+        let previews = preview_rename_internal(&db_path, file_paths.clone(), sequence).unwrap();
+        assert_eq!(new_names(&previews), vec!["a_001.txt", "b_000.txt", "c_-01.txt"]);
+
+        let template = vec![rule(serde_json::json!({
+            "ruleType": "template",
+            "template": "{n:03}{ext}",
+            "step": -2,
+        }))];
+        let previews = preview_rename_internal(&db_path, file_paths, template).unwrap();
+        assert_eq!(new_names(&previews), vec!["001.txt", "-01.txt", "-03.txt"]);
+    }
+
+    #[test]
+    fn test_template_output_is_split_into_stem_and_extension() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("x.jpg");
+        fs::write(&path, b"data").unwrap();
+
+        let rules = vec![
+            rule(serde_json::json!({ "ruleType": "template", "template": "{name}.backup.txt" })),
+            rule(serde_json::json!({
+                "ruleType": "extension",
+                "extensionAction": "set",
+                "newExtension": "md",
+            })),
+            rule(serde_json::json!({ "ruleType": "suffix", "suffix": "_1" })),
+        ];
+        let file_paths = vec![path.to_string_lossy().to_string()];
+        let db_path = dir.path().join("test.db");
+        let previews = preview_rename_internal(&db_path, file_paths, rules).unwrap();
+
+        assert_eq!(new_names(&previews), vec!["x.backup_1.md"]);
+    }
+
+    #[test]
+    fn test_rule_conditions_limit_the_files_changed() {
+        let dir = tempdir().unwrap();
+        let mut file_paths = Vec::new();
+        for name in ["photo.jpg", "notes.txt"] {
+            let path = dir.path().join(name);
+            fs::write(&path, b"data").unwrap();
+            file_paths.push(path.to_string_lossy().to_string());
+        }
+
+        let rules = vec![
+            rule(serde_json::json!({
+                "ruleType": "prefix",
+                "prefix": "IMG_",
+                "conditions": [{ "field": "extension", "operator": "in", "value": "jpg, png" }],
+            })),
+            // Conditions see the original names, not the prefixed ones
+            rule(serde_json::json!({
+                "ruleType": "suffix",
+                "suffix": "_old",
+                "conditions": [{ "field": "name", "operator": "startsWith", "value": "IMG_" }],
+            })),
+            rule(serde_json::json!({ "ruleType": "case", "caseType": "upper" })),
+        ];
+        let db_path = dir.path().join("test.db");
+        let previews = preview_rename_internal(&db_path, file_paths, rules).unwrap();
+
+        assert_eq!(new_names(&previews), vec!["IMG_PHOTO.jpg", "NOTES.txt"]);
+    }
+
+    #[test]
+    fn test_nfc_and_nfd_names_conflict_only_in_the_same_folder() {
+        let dir = tempdir().unwrap();
+        let mut file_paths = Vec::new();
+        for (folder, name) in [("A", "p1.txt"), ("A", "q2.txt"), ("B", "q3.txt")] {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+            let path = dir.path().join(folder).join(name);
+            fs::write(&path, b"data").unwrap();
+            file_paths.push(path.to_string_lossy().to_string());
+        }
+
+        let nfc = to_nfc("회의록");
+        let nfd = to_nfd("회의록");
+        assert_ne!(nfc, nfd);
+        let rules = vec![
+            rule(serde_json::json!({
+                "ruleType": "template",
+                "template": format!("{}{{ext}}", nfc),
+                "conditions": [{ "field": "name", "operator": "startsWith", "value": "p" }],
+            })),
+            rule(serde_json::json!({
+                "ruleType": "template",
+                "template": format!("{}{{ext}}", nfd),
+                "conditions": [{ "field": "name", "operator": "startsWith", "value": "q" }],
+            })),
+        ];
+        let db_path = dir.path().join("test.db");
+        let previews = preview_rename_internal(&db_path, file_paths, rules).unwrap();
+
+        let conflicts: Vec<bool> = previews.iter().map(|p| p.has_conflict).collect();
+        assert_eq!(conflicts, vec![false, true, false]);
+        assert_eq!(
+            previews[1].conflict_message.as_deref(),
+            Some("이름이 중복됩니다")
+        );
+    }
+
+    #[test]
+    fn test_category_change_warning() {
+        let dir = tempdir().unwrap();
+        let mut file_paths = Vec::new();
+        for name in ["photo.jpg", "scan.PNG"] {
+            let path = dir.path().join(name);
+            fs::write(&path, b"data").unwrap();
+            file_paths.push(path.to_string_lossy().to_string());
+        }
+        let db_path = dir.path().join("test.db");
+
+        let set_txt = vec![rule(serde_json::json!({
+            "ruleType": "extension",
+            "extensionAction": "set",
+            "newExtension": ".txt",
+        }))];
+        let previews = preview_rename_internal(&db_path, file_paths.clone(), set_txt).unwrap();
+        assert_eq!(new_names(&previews), vec!["photo.txt", "scan.txt"]);
+        assert!(previews.iter().all(|p| p.warning.is_some()));
+
+        let lower = vec![rule(serde_json::json!({ "ruleType": "extension" }))];
+        let previews = preview_rename_internal(&db_path, file_paths, lower).unwrap();
+        assert_eq!(new_names(&previews), vec!["photo.jpg", "scan.png"]);
+        assert!(previews.iter().all(|p| p.warning.is_none()));
+    }
+
+    #[test]
+    fn test_bad_templates_and_names_are_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("x.txt");
+        fs::write(&path, b"data").unwrap();
+        let file_paths = vec![path.to_string_lossy().to_string()];
+        let db_path = dir.path().join("test.db");
+
+        let template = |t: &str| {
+            vec![rule(
+                serde_json::json!({ "ruleType": "template", "template": t }),
+            )]
+        };
+
+        // An invalid date format fails the preview instead of panicking
+        assert!(
+            preview_rename_internal(&db_path, file_paths.clone(), template("{date:%Q}")).is_err()
+        );
+        let bad_date = vec![rule(
+            serde_json::json!({ "ruleType": "date", "dateFormat": "%Y-%" }),
+        )];
+        assert!(preview_rename_internal(&db_path, file_paths.clone(), bad_date).is_err());
+
+        for bad in [
+            "{date:%Y/%m}_{name}{ext}",
+            "{name|replace:x:../}{ext}",
+            "..",
+            "",
+            "a\\b",
+        ] {
+            let previews =
+                preview_rename_internal(&db_path, file_paths.clone(), template(bad)).unwrap();
+            assert!(previews[0].has_conflict, "{:?} was accepted", bad);
+            assert!(previews[0].conflict_message.is_some());
+        }
+
+        // Executing skips such names and leaves the file where it was
+        let result =
+            execute_rename_internal(&db_path, file_paths, template("../{name}{ext}")).unwrap();
+        assert_eq!(result.renamed_count, 0);
+        assert_eq!(result.failed_count, 1);
+        assert!(path.exists());
+        assert!(!dir.path().parent().unwrap().join("x.txt").exists());
+    }
+}
@@ -1,12 +1,23 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
+use crate::commands::jobs::JobState;
+use crate::commands::organizer::get_unique_path;
+use crate::commands::pins::PinMatcher;
+use crate::commands::quarantine::{self, Removal};
 use crate::commands::scanner::FileInfo;
 use crate::database::DbPath;
 use crate::services::classifier::classify_extension;
+use crate::services::date_source::{resolve_date_folder, DateSource};
+use crate::services::link_policy::{link_target, WalkPolicy};
+use crate::services::metadata::MetadataCache;
+use crate::services::mover;
+use crate::services::rename_template::{RenameTemplate, TemplateContext};
+use crate::services::settle::{DeferReason, DeferredFile, SettleChecker};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,13 +32,15 @@ pub struct Rule {
     pub action_destination: Option<String>,
     pub action_rename_pattern: Option<String>,
     pub create_date_subfolder: bool,
+    #[serde(default)]
+    pub date_source: DateSource,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Condition {
-    pub field: String,    // "name", "extension", "size", "createdDate", "modifiedDate"
-    pub operator: String, // "equals", "contains", "startsWith", "endsWith", "greaterThan", "lessThan", "matches"
+    pub field: String,    // "name", "extension", "size", "createdDate", "modifiedDate", "category"
+    pub operator: String, // "equals", "contains", "startsWith", "endsWith", "in", "greaterThan", "lessThan", "matches"
     pub value: String,
 }
 
@@ -48,7 +61,8 @@ pub fn get_rules(db_state: State<DbPath>) -> Result<Vec<Rule>, String> {
     let mut stmt = conn
         .prepare(
             "SELECT id, name, priority, enabled, conditions, condition_logic,
-             action_type, action_destination, action_rename_pattern, create_date_subfolder
+             action_type, action_destination, action_rename_pattern, create_date_subfolder,
+             COALESCE(date_source, 'modified')
              FROM rules ORDER BY priority DESC",
         )
         .map_err(|e| e.to_string())?;
@@ -70,6 +84,7 @@ pub fn get_rules(db_state: State<DbPath>) -> Result<Vec<Rule>, String> {
                 action_destination: row.get(7)?,
                 action_rename_pattern: row.get(8)?,
                 create_date_subfolder: row.get::<_, i32>(9)? != 0,
+                date_source: DateSource::parse(&row.get::<_, String>(10)?),
             })
         })
         .map_err(|e| e.to_string())?;
@@ -97,8 +112,9 @@ pub fn save_rule(db_state: State<DbPath>, rule: Rule) -> Result<Rule, String> {
         conn.execute(
             "UPDATE rules SET name = ?1, priority = ?2, enabled = ?3, conditions = ?4,
              condition_logic = ?5, action_type = ?6, action_destination = ?7,
-             action_rename_pattern = ?8, create_date_subfolder = ?9, updated_at = CURRENT_TIMESTAMP
-             WHERE id = ?10",
+             action_rename_pattern = ?8, create_date_subfolder = ?9, date_source = ?10,
+             updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?11",
             rusqlite::params![
                 rule.name,
                 rule.priority,
@@ -109,6 +125,7 @@ pub fn save_rule(db_state: State<DbPath>, rule: Rule) -> Result<Rule, String> {
                 rule.action_destination,
                 rule.action_rename_pattern,
                 rule.create_date_subfolder as i32,
+                rule.date_source.as_str(),
                 id,
             ],
         )
@@ -119,8 +136,8 @@ pub fn save_rule(db_state: State<DbPath>, rule: Rule) -> Result<Rule, String> {
         // Create new rule
         conn.execute(
             "INSERT INTO rules (name, priority, enabled, conditions, condition_logic,
-             action_type, action_destination, action_rename_pattern, create_date_subfolder)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+             action_type, action_destination, action_rename_pattern, create_date_subfolder, date_source)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             rusqlite::params![
                 rule.name,
                 rule.priority,
@@ -131,6 +148,7 @@ pub fn save_rule(db_state: State<DbPath>, rule: Rule) -> Result<Rule, String> {
                 rule.action_destination,
                 rule.action_rename_pattern,
                 rule.create_date_subfolder as i32,
+                rule.date_source.as_str(),
             ],
         )
         .map_err(|e| e.to_string())?;
@@ -161,76 +179,25 @@ pub fn preview_rules(
     db_state: State<DbPath>,
     source_path: String,
 ) -> Result<Vec<RuleMatch>, String> {
-    let rules = get_rules(db_state)?;
+    let db_path = &db_state.0;
+    let rules = get_rules_internal(db_path)?;
     let enabled_rules: Vec<Rule> = rules.into_iter().filter(|r| r.enabled).collect();
 
     if enabled_rules.is_empty() {
         return Ok(Vec::new());
     }
 
-    let source = PathBuf::from(&source_path);
-    if !source.exists() {
-        return Err("경로가 존재하지 않습니다".to_string());
-    }
-
-    let mut matches: Vec<RuleMatch> = Vec::new();
-
-    let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if path.is_dir() {
-            continue;
-        }
-
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        if file_name.starts_with('.') {
-            continue;
-        }
-
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{}", e.to_lowercase()))
-            .unwrap_or_default();
-
-        let metadata = fs::metadata(&path).ok();
-        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-        let category = classify_extension(&extension);
-
-        let file_info = FileInfo {
-            path: path.to_string_lossy().to_string(),
-            name: file_name.clone(),
-            extension: extension.clone(),
-            size,
-            size_formatted: format_size(size),
-            created_at: get_time(&metadata, true),
-            modified_at: get_time(&metadata, false),
-            is_directory: false,
-            is_hidden: file_name.starts_with('.'),
-            category,
-        };
-
-        // Check each rule
-        for rule in &enabled_rules {
-            if evaluate_rule(&file_info, rule) {
-                let action_preview = format_action_preview(rule, &file_info);
-                matches.push(RuleMatch {
-                    file: file_info.clone(),
-                    rule: rule.clone(),
-                    action_preview,
-                });
-                break; // First matching rule wins
-            }
-        }
-    }
-
+    let pins = PinMatcher::load(db_path);
+    let deferred = SettleChecker::load(db_path).check_dir(Path::new(&source_path));
+    let walk_policy = WalkPolicy::load(db_path);
+    let (matches, _) = compute_matches(
+        &source_path,
+        &enabled_rules,
+        &pins,
+        &deferred,
+        &walk_policy,
+        &mut MetadataCache::new(),
+    )?;
     Ok(matches)
 }
 
@@ -238,6 +205,7 @@ pub fn preview_rules(
 #[tauri::command]
 pub fn execute_rules(
     db_state: State<DbPath>,
+    jobs: State<JobState>,
     source_path: String,
 ) -> Result<ExecuteRulesResult, String> {
     let db_path = db_state.0.clone();
@@ -251,47 +219,107 @@ pub fn execute_rules(
             success: true,
             executed_count: 0,
             skipped_count: 0,
+            deferred_count: 0,
+            deferred: vec![],
             errors: vec![],
         });
     }
 
-    let matches = compute_matches(&source_path, &enabled_rules)?;
-
-    let mut executed_count = 0;
-    let mut skipped_count = 0;
-    let mut errors: Vec<String> = Vec::new();
-    let mut move_details: Vec<(String, String)> = Vec::new();
-
-    for rule_match in matches {
-        let result = execute_action(&rule_match.rule, &rule_match.file);
-        match result {
-            Ok(new_path) => {
-                move_details.push((rule_match.file.path.clone(), new_path));
-                executed_count += 1;
-            }
-            Err(e) => {
-                errors.push(format!("{}: {}", rule_match.file.name, e));
-                skipped_count += 1;
+    let pins = PinMatcher::load(&db_path);
+    let deferred = SettleChecker::load(&db_path).check_dir(Path::new(&source_path));
+    let walk_policy = WalkPolicy::load(&db_path);
+    // Shared by matching and execution so each file's metadata is read once
+    let mut metadata_cache = MetadataCache::new();
+    let (matches, deferred_files) = compute_matches(
+        &source_path,
+        &enabled_rules,
+        &pins,
+        &deferred,
+        &walk_policy,
+        &mut metadata_cache,
+    )?;
+
+    // The source and every destination stay locked against queued jobs while files move
+    let mut paths = vec![PathBuf::from(&source_path)];
+    for rule_match in &matches {
+        if let Some(dest) = &rule_match.rule.action_destination {
+            let file_path = Path::new(&rule_match.file.path);
+            let date_source = rule_match.rule.date_source;
+            let dest = PathBuf::from(render_destination(
+                dest,
+                file_path,
+                date_source,
+                &mut metadata_cache,
+            ));
+            if !paths.contains(&dest) {
+                paths.push(dest);
             }
         }
     }
 
-    // Record history
-    if executed_count > 0 {
-        let details_json = serde_json::to_string(&move_details).unwrap_or_default();
-        let _ = crate::database::add_history(
-            &db_path,
-            "organize",
-            &format!("규칙 기반 정리: {}개 파일 처리", executed_count),
-            &details_json,
-        );
-    }
+    jobs.0.run_locked(paths, |_| {
+        let mut executed_count = 0;
+        let mut skipped_count = 0;
+        let mut errors: Vec<String> = Vec::new();
+        let mut move_details: Vec<(String, String)> = Vec::new();
+        let mut copied_paths: Vec<String> = Vec::new();
+        let mut trashed: Vec<(String, i64)> = Vec::new();
+        let mut quarantined: Vec<i64> = Vec::new();
+
+        for rule_match in matches {
+            let result = execute_action(
+                &db_path,
+                &rule_match.rule,
+                &rule_match.file,
+                &mut metadata_cache,
+            );
+            match result {
+                Ok(outcome) => {
+                    match outcome {
+                        ActionOutcome::Moved(new_path) => {
+                            move_details.push((rule_match.file.path.clone(), new_path))
+                        }
+                        ActionOutcome::Copied(copy_path) => copied_paths.push(copy_path),
+                        ActionOutcome::Removed(Removal::Trashed(deleted_at)) => {
+                            trashed.push((rule_match.file.path.clone(), deleted_at))
+                        }
+                        ActionOutcome::Removed(Removal::Quarantined(id)) => quarantined.push(id),
+                    }
+                    executed_count += 1;
+                }
+                Err(e) => {
+                    errors.push(format!("{}: {}", rule_match.file.name, e));
+                    skipped_count += 1;
+                }
+            }
+        }
+
+        // Record history
+        if executed_count > 0 {
+            let details_json = serde_json::json!({
+                "action": "organize",
+                "moves": move_details,
+                "copied_paths": copied_paths,
+                "trashed": trashed,
+                "quarantined": quarantined,
+            })
+            .to_string();
+            let _ = crate::database::add_history(
+                &db_path,
+                "organize",
+                &format!("규칙 기반 정리: {}개 파일 처리", executed_count),
+                &details_json,
+            );
+        }
 
-    Ok(ExecuteRulesResult {
-        success: errors.is_empty(),
-        executed_count,
-        skipped_count,
-        errors,
+        Ok(ExecuteRulesResult {
+            success: errors.is_empty(),
+            executed_count,
+            skipped_count,
+            deferred_count: deferred_files.len(),
+            deferred: deferred_files,
+            errors,
+        })
     })
 }
 
@@ -301,19 +329,22 @@ pub struct ExecuteRulesResult {
     pub success: bool,
     pub executed_count: usize,
     pub skipped_count: usize,
+    pub deferred_count: usize,
+    pub deferred: Vec<DeferredFile>,
     pub errors: Vec<String>,
 }
 
 // Helper functions
 
 /// Internal function to get rules without State wrapper
-fn get_rules_internal(db_path: &PathBuf) -> Result<Vec<Rule>, String> {
+pub(crate) fn get_rules_internal(db_path: &PathBuf) -> Result<Vec<Rule>, String> {
     let conn = rusqlite::Connection::open(db_path).map_err(|e| e.to_string())?;
 
     let mut stmt = conn
         .prepare(
             "SELECT id, name, priority, enabled, conditions, condition_logic,
-             action_type, action_destination, action_rename_pattern, create_date_subfolder
+             action_type, action_destination, action_rename_pattern, create_date_subfolder,
+             COALESCE(date_source, 'modified')
              FROM rules ORDER BY priority DESC",
         )
         .map_err(|e| e.to_string())?;
@@ -335,6 +366,7 @@ fn get_rules_internal(db_path: &PathBuf) -> Result<Vec<Rule>, String> {
                 action_destination: row.get(7)?,
                 action_rename_pattern: row.get(8)?,
                 create_date_subfolder: row.get::<_, i32>(9)? != 0,
+                date_source: DateSource::parse(&row.get::<_, String>(10)?),
             })
         })
         .map_err(|e| e.to_string())?;
@@ -349,22 +381,32 @@ fn get_rules_internal(db_path: &PathBuf) -> Result<Vec<Rule>, String> {
     Ok(result)
 }
 
-/// Compute matches for files in source_path against rules
-fn compute_matches(source_path: &str, enabled_rules: &[Rule]) -> Result<Vec<RuleMatch>, String> {
+/// Compute matches for files in source_path against rules.
+/// Also returns the files that matched a rule but were deferred (still being written or in use).
+fn compute_matches(
+    source_path: &str,
+    enabled_rules: &[Rule],
+    pins: &PinMatcher,
+    deferred: &HashMap<PathBuf, DeferReason>,
+    walk_policy: &WalkPolicy,
+    metadata_cache: &mut MetadataCache,
+) -> Result<(Vec<RuleMatch>, Vec<DeferredFile>), String> {
     let source = PathBuf::from(source_path);
     if !source.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
     }
 
     let mut matches: Vec<RuleMatch> = Vec::new();
+    let mut deferred_files: Vec<DeferredFile> = Vec::new();
     let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
 
     for entry in entries.flatten() {
         let path = entry.path();
 
-        if path.is_dir() {
+        // Skip folders, links to folders and entries the link policy leaves out
+        let Some(metadata) = walk_policy.file_metadata(&path) else {
             continue;
-        }
+        };
 
         let file_name = path
             .file_name()
@@ -376,13 +418,21 @@ fn compute_matches(source_path: &str, enabled_rules: &[Rule]) -> Result<Vec<Rule
             continue;
         }
 
+        // Pinned files never match a rule
+        if pins.is_pinned(&path) {
+            continue;
+        }
+        // Files still being written are matched only to be reported as deferred
+        let defer_reason = deferred.get(&path).copied();
+
         let extension = path
             .extension()
             .and_then(|e| e.to_str())
             .map(|e| format!(".{}", e.to_lowercase()))
             .unwrap_or_default();
 
-        let metadata = fs::metadata(&path).ok();
+        let link_target = link_target(&path);
+        let metadata = Some(metadata);
         let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
         let category = classify_extension(&extension);
 
@@ -397,12 +447,21 @@ fn compute_matches(source_path: &str, enabled_rules: &[Rule]) -> Result<Vec<Rule
             is_directory: false,
             is_hidden: file_name.starts_with('.'),
             category,
+            is_symlink: link_target.is_some(),
+            link_target,
         };
 
         // Check each rule
         for rule in enabled_rules {
             if evaluate_rule(&file_info, rule) {
-                let action_preview = format_action_preview(rule, &file_info);
+                if let Some(reason) = defer_reason {
+                    deferred_files.push(DeferredFile {
+                        path: file_info.path.clone(),
+                        reason,
+                    });
+                    break;
+                }
+                let action_preview = format_action_preview(rule, &file_info, metadata_cache);
                 matches.push(RuleMatch {
                     file: file_info.clone(),
                     rule: rule.clone(),
@@ -413,24 +472,22 @@ fn compute_matches(source_path: &str, enabled_rules: &[Rule]) -> Result<Vec<Rule
         }
     }
 
-    Ok(matches)
+    Ok((matches, deferred_files))
 }
 
 fn evaluate_rule(file: &FileInfo, rule: &Rule) -> bool {
-    let results: Vec<bool> = rule
-        .conditions
-        .iter()
-        .map(|c| evaluate_condition(file, c))
-        .collect();
+    !rule.conditions.is_empty()
+        && evaluate_conditions(file, &rule.conditions, &rule.condition_logic)
+}
 
-    if results.is_empty() {
-        return false;
-    }
+/// Evaluate conditions joined by "AND" or "OR" (an empty list matches everything)
+pub(crate) fn evaluate_conditions(file: &FileInfo, conditions: &[Condition], logic: &str) -> bool {
+    let mut results = conditions.iter().map(|c| evaluate_condition(file, c));
 
-    if rule.condition_logic == "AND" {
-        results.iter().all(|&r| r)
+    if logic == "AND" {
+        results.all(|r| r)
     } else {
-        results.iter().any(|&r| r)
+        conditions.is_empty() || results.any(|r| r)
     }
 }
 
@@ -441,6 +498,7 @@ fn evaluate_condition(file: &FileInfo, condition: &Condition) -> bool {
         "size" => file.size.to_string(),
         "createdDate" => file.created_at.clone(),
         "modifiedDate" => file.modified_at.clone(),
+        "category" => format!("{:?}", file.category).to_lowercase(),
         _ => return false,
     };
 
@@ -449,6 +507,14 @@ fn evaluate_condition(file: &FileInfo, condition: &Condition) -> bool {
         "contains" => field_value.to_lowercase().contains(&condition.value.to_lowercase()),
         "startsWith" => field_value.to_lowercase().starts_with(&condition.value.to_lowercase()),
         "endsWith" => field_value.to_lowercase().ends_with(&condition.value.to_lowercase()),
+        // Comma-separated list, e.g. "jpg, .png, heic" (a leading dot is optional)
+        "in" => {
+            let value = field_value.trim_start_matches('.').to_lowercase();
+            condition
+                .value
+                .split(',')
+                .any(|item| item.trim().trim_start_matches('.').to_lowercase() == value)
+        }
         "greaterThan" => {
             if let (Ok(a), Ok(b)) = (field_value.parse::<u64>(), condition.value.parse::<u64>()) {
                 a > b
@@ -474,18 +540,22 @@ fn evaluate_condition(file: &FileInfo, condition: &Condition) -> bool {
     }
 }
 
-fn format_action_preview(rule: &Rule, file: &FileInfo) -> String {
+fn format_action_preview(rule: &Rule, file: &FileInfo, metadata_cache: &mut MetadataCache) -> String {
+    let resolved = rule.action_destination.as_ref().map(|dest| {
+        render_destination(dest, Path::new(&file.path), rule.date_source, metadata_cache)
+    });
+
     match rule.action_type.as_str() {
         "move" => {
-            if let Some(dest) = &rule.action_destination {
-                format!("이동: {} → {}", file.name, dest)
+            if let Some(dest) = &resolved {
+                format!("이동: {} → {}", file.name, format_destination_preview(rule, file, dest))
             } else {
                 format!("이동: {}", file.name)
             }
         }
         "copy" => {
-            if let Some(dest) = &rule.action_destination {
-                format!("복사: {} → {}", file.name, dest)
+            if let Some(dest) = &resolved {
+                format!("복사: {} → {}", file.name, format_destination_preview(rule, file, dest))
             } else {
                 format!("복사: {}", file.name)
             }
@@ -502,7 +572,76 @@ fn format_action_preview(rule: &Rule, file: &FileInfo) -> String {
     }
 }
 
-fn execute_action(rule: &Rule, file: &FileInfo) -> Result<String, String> {
+/// Destination shown in previews, including the date subfolder and the date source used
+fn format_destination_preview(rule: &Rule, file: &FileInfo, dest: &str) -> String {
+    if !rule.create_date_subfolder {
+        return dest.to_string();
+    }
+
+    match rule_date_folder(&file.path, rule.date_source) {
+        (date_folder, Some(source)) => {
+            format!("{}/{} ({} 기준)", dest, date_folder, source.korean_label())
+        }
+        (date_folder, None) => format!("{}/{}", dest, date_folder),
+    }
+}
+
+/// Destination folder for one file. Destinations using rename template tokens
+/// (e.g. "~/Music/{artist}/{album}") are rendered; anything else, including paths with
+/// braces that are not tokens like "D:\Backup {old}", is used as written.
+/// `{date}` uses the rule's date source.
+fn render_destination(
+    destination: &str,
+    path: &Path,
+    date_source: DateSource,
+    metadata_cache: &mut MetadataCache,
+) -> String {
+    if !destination.contains('{') {
+        return destination.to_string();
+    }
+    // Only known tokens parse, so a failed parse means the braces are part of the path
+    let Ok(template) = RenameTemplate::parse(destination) else {
+        return destination.to_string();
+    };
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let metadata = if template.uses_metadata() {
+        Some(metadata_cache.get(path))
+    } else {
+        None
+    };
+
+    template.render(&TemplateContext {
+        path,
+        stem: &stem,
+        extension: &extension,
+        counter: 1,
+        total: 1,
+        date_source,
+        metadata,
+    })
+}
+
+/// What a rule action did to a file, recorded for undo
+enum ActionOutcome {
+    Moved(String),
+    Copied(String),
+    /// Sent to the quarantine or the trash
+    Removed(Removal),
+}
+
+fn execute_action(
+    db_path: &PathBuf,
+    rule: &Rule,
+    file: &FileInfo,
+    metadata_cache: &mut MetadataCache,
+) -> Result<ActionOutcome, String> {
     let source_path = PathBuf::from(&file.path);
 
     match rule.action_type.as_str() {
@@ -512,27 +651,30 @@ fn execute_action(rule: &Rule, file: &FileInfo) -> Result<String, String> {
                 .as_ref()
                 .ok_or("대상 폴더가 지정되지 않았습니다")?;
 
-            let mut dest_path = PathBuf::from(dest_folder);
+            let mut dest_path = PathBuf::from(render_destination(
+                dest_folder,
+                &source_path,
+                rule.date_source,
+                metadata_cache,
+            ));
 
             if rule.create_date_subfolder {
-                let date_folder = &file.modified_at[..7]; // YYYY-MM
+                let (date_folder, _) = rule_date_folder(&file.path, rule.date_source);
                 dest_path = dest_path.join(date_folder);
             }
 
             fs::create_dir_all(&dest_path).map_err(|e| e.to_string())?;
 
-            let final_path = dest_path.join(&file.name);
+            // A rename would silently replace an existing file, so pick a free name
+            let mut final_path = dest_path.join(&file.name);
+            if fs::symlink_metadata(&final_path).is_ok() {
+                final_path = get_unique_path(&final_path);
+            }
 
-            fs::rename(&source_path, &final_path).map_err(|e| {
-                // Try copy + delete for cross-device moves
-                if let Err(copy_err) = fs::copy(&source_path, &final_path) {
-                    return format!("이동 실패: {}", copy_err);
-                }
-                let _ = fs::remove_file(&source_path);
-                e.to_string()
-            })?;
+            mover::move_file(&source_path, &final_path)
+                .map_err(|e| format!("이동 실패: {}", e))?;
 
-            Ok(final_path.to_string_lossy().to_string())
+            Ok(ActionOutcome::Moved(final_path.to_string_lossy().to_string()))
         }
         "copy" => {
             let dest_folder = rule
@@ -540,28 +682,45 @@ fn execute_action(rule: &Rule, file: &FileInfo) -> Result<String, String> {
                 .as_ref()
                 .ok_or("대상 폴더가 지정되지 않았습니다")?;
 
-            let mut dest_path = PathBuf::from(dest_folder);
+            let mut dest_path = PathBuf::from(render_destination(
+                dest_folder,
+                &source_path,
+                rule.date_source,
+                metadata_cache,
+            ));
 
             if rule.create_date_subfolder {
-                let date_folder = &file.modified_at[..7];
+                let (date_folder, _) = rule_date_folder(&file.path, rule.date_source);
                 dest_path = dest_path.join(date_folder);
             }
 
             fs::create_dir_all(&dest_path).map_err(|e| e.to_string())?;
 
-            let final_path = dest_path.join(&file.name);
-            fs::copy(&source_path, &final_path).map_err(|e| e.to_string())?;
+            // Never copy over an existing file: undo removes the copy it recorded
+            let mut final_path = dest_path.join(&file.name);
+            if fs::symlink_metadata(&final_path).is_ok() {
+                final_path = get_unique_path(&final_path);
+            }
+            mover::copy_file_verified(&source_path, &final_path)
+                .map_err(|e| format!("복사 실패: {}", e))?;
 
-            Ok(final_path.to_string_lossy().to_string())
+            Ok(ActionOutcome::Copied(final_path.to_string_lossy().to_string()))
         }
         "delete" => {
-            trash::delete(&source_path).map_err(|e| e.to_string())?;
-            Ok(file.path.clone())
+            let reason = format!("규칙: {}", rule.name);
+            quarantine::remove_file(db_path, &source_path, &reason).map(ActionOutcome::Removed)
         }
         _ => Err("지원되지 않는 작업입니다".to_string()),
     }
 }
 
+/// Resolve the YYYY-MM date subfolder for a rule, using the rule's date source
+fn rule_date_folder(file_path: &str, date_source: DateSource) -> (String, Option<DateSource>) {
+    let path = PathBuf::from(file_path);
+    let metadata = fs::metadata(&path).ok();
+    resolve_date_folder(&path, metadata.as_ref(), date_source, "YYYY-MM")
+}
+
 fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -608,6 +767,8 @@ pub struct DefaultRule {
     pub destination: String,
     pub create_date_subfolder: bool,
     pub priority: i32,
+    #[serde(default)]
+    pub date_source: DateSource,
 }
 
 /// Get default category rules
@@ -624,7 +785,8 @@ pub fn get_default_rules(db_state: State<DbPath>) -> Result<Vec<DefaultRule>, St
             enabled INTEGER NOT NULL DEFAULT 1,
             destination TEXT NOT NULL,
             create_date_subfolder INTEGER DEFAULT 0,
-            priority INTEGER DEFAULT 0
+            priority INTEGER DEFAULT 0,
+            date_source TEXT DEFAULT 'modified'
         )",
         [],
     )
@@ -632,10 +794,11 @@ pub fn get_default_rules(db_state: State<DbPath>) -> Result<Vec<DefaultRule>, St
 
     // Add priority column if it doesn't exist (migration for existing DBs)
     let _ = conn.execute("ALTER TABLE default_rules ADD COLUMN priority INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE default_rules ADD COLUMN date_source TEXT DEFAULT 'modified'", []);
 
     // Get existing rules ordered by priority
     let mut stmt = conn
-        .prepare("SELECT id, category, enabled, destination, create_date_subfolder, COALESCE(priority, 0) FROM default_rules ORDER BY priority ASC")
+        .prepare("SELECT id, category, enabled, destination, create_date_subfolder, COALESCE(priority, 0), COALESCE(date_source, 'modified') FROM default_rules ORDER BY priority ASC")
         .map_err(|e| e.to_string())?;
 
     let rules: Vec<DefaultRule> = stmt
@@ -647,6 +810,7 @@ pub fn get_default_rules(db_state: State<DbPath>) -> Result<Vec<DefaultRule>, St
                 destination: row.get(3)?,
                 create_date_subfolder: row.get::<_, i32>(4)? != 0,
                 priority: row.get(5)?,
+                date_source: DateSource::parse(&row.get::<_, String>(6)?),
             })
         })
         .map_err(|e| e.to_string())?
@@ -688,12 +852,13 @@ pub fn save_default_rule(db_state: State<DbPath>, rule: DefaultRule) -> Result<D
     let conn = rusqlite::Connection::open(db_path).map_err(|e| e.to_string())?;
 
     conn.execute(
-        "UPDATE default_rules SET enabled = ?1, destination = ?2, create_date_subfolder = ?3, priority = ?4 WHERE id = ?5",
+        "UPDATE default_rules SET enabled = ?1, destination = ?2, create_date_subfolder = ?3, priority = ?4, date_source = ?5 WHERE id = ?6",
         rusqlite::params![
             rule.enabled as i32,
             rule.destination,
             rule.create_date_subfolder as i32,
             rule.priority,
+            rule.date_source.as_str(),
             rule.id,
         ],
     )
@@ -710,11 +875,15 @@ pub fn save_default_rule(db_state: State<DbPath>, rule: DefaultRule) -> Result<D
 #[serde(rename_all = "camelCase")]
 pub struct UnifiedPreview {
     pub file: FileInfo,
-    pub match_type: String, // "custom" or "default"
+    pub match_type: String, // "custom", "default", "kept" (pinned) or "deferred"
     pub rule: Option<Rule>,
     pub default_rule: Option<DefaultRule>,
     pub action: String,
     pub destination: String,
+    pub date_folder: Option<String>,
+    pub date_source: Option<DateSource>,
+    /// Why a "deferred" file was left in place
+    pub defer_reason: Option<DeferReason>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -723,6 +892,8 @@ pub struct UnifiedOrganizeResult {
     pub success: bool,
     pub files_moved: usize,
     pub files_skipped: usize,
+    pub files_deferred: usize,
+    pub deferred: Vec<DeferredFile>,
     pub errors: Vec<String>,
     pub history_id: i64,
 }
@@ -733,211 +904,189 @@ pub fn preview_unified(
     db_state: State<DbPath>,
     source_path: String,
 ) -> Result<Vec<UnifiedPreview>, String> {
-    let db_path = &db_state.0;
+    preview_unified_internal(&db_state.0, &source_path)
+}
 
-    // Get custom rules
-    let custom_rules = get_rules_internal(db_path)?;
-    let enabled_custom_rules: Vec<Rule> = custom_rules.into_iter().filter(|r| r.enabled).collect();
+/// Execute unified organization
+#[tauri::command]
+pub fn execute_unified(
+    db_state: State<DbPath>,
+    jobs: State<JobState>,
+    source_path: String,
+    excluded_destinations: Option<Vec<String>>,
+) -> Result<UnifiedOrganizeResult, String> {
+    let db_path = db_state.0.clone();
+    let previews = preview_unified_internal(&db_path, &source_path)?;
+    let excluded = excluded_destinations.unwrap_or_default();
 
-    // Get default rules
-    let default_rules = get_default_rules_internal(db_path)?;
-    let enabled_default_rules: Vec<DefaultRule> = default_rules.into_iter().filter(|r| r.enabled).collect();
+    let mut paths = vec![PathBuf::from(&source_path)];
+    paths.extend(preview_destinations(&previews));
+    let outcome = jobs.0.run_locked(paths, |_| {
+        Ok(move_previews(&db_path, previews, &excluded, "overwrite"))
+    })?;
 
-    let source = PathBuf::from(&source_path);
-    if !source.exists() {
-        return Err("경로가 존재하지 않습니다".to_string());
-    }
+    // Record history
+    let details_json = serde_json::json!({
+        "action": "organize",
+        "moves": outcome.move_details,
+        "trashed": outcome.trashed,
+        "quarantined": outcome.quarantined,
+    })
+    .to_string();
+    let history_id = crate::database::add_history(
+        &db_path,
+        "organize",
+        &format!("통합 정리: {}개 파일 이동", outcome.files_moved),
+        &details_json,
+    )
+    .unwrap_or(-1);
 
-    let mut previews: Vec<UnifiedPreview> = Vec::new();
-    let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
+    Ok(UnifiedOrganizeResult {
+        success: outcome.errors.is_empty(),
+        files_moved: outcome.files_moved,
+        files_skipped: outcome.files_skipped,
+        files_deferred: outcome.files_deferred,
+        deferred: outcome.deferred,
+        errors: outcome.errors,
+        history_id,
+    })
+}
 
-    for entry in entries.flatten() {
-        let path = entry.path();
+/// Counts and move log of a unified execution, before history is recorded
+#[derive(Debug, Default)]
+pub(crate) struct MoveOutcome {
+    pub files_moved: usize,
+    pub files_skipped: usize,
+    pub files_deferred: usize,
+    pub deferred: Vec<DeferredFile>,
+    pub errors: Vec<String>,
+    pub move_details: Vec<(String, String)>,
+    /// Existing files an overwrite replaced, sent to the trash or the quarantine
+    pub trashed: Vec<(String, i64)>,
+    pub quarantined: Vec<i64>,
+}
 
-        if path.is_dir() {
+/// Folders a unified preview moves files into, for the job path locks
+pub(crate) fn preview_destinations(previews: &[UnifiedPreview]) -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = Vec::new();
+    for preview in previews {
+        if matches!(preview.match_type.as_str(), "kept" | "deferred") {
             continue;
         }
-
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        if file_name.starts_with('.') {
-            continue;
+        let folder = PathBuf::from(&preview.destination);
+        if !folders.contains(&folder) {
+            folders.push(folder);
         }
+    }
+    folders
+}
 
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| format!(".{}", e.to_lowercase()))
-            .unwrap_or_default();
-
-        let metadata = fs::metadata(&path).ok();
-        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-        let category = classify_extension(&extension);
+/// Move the files of a unified preview to their destinations.
+/// `handle_duplicates` is "overwrite", "rename" or "skip"; a file an overwrite
+/// replaces goes to the trash or the quarantine so undo can bring it back.
+pub(crate) fn move_previews(
+    db_path: &PathBuf,
+    previews: Vec<UnifiedPreview>,
+    excluded: &[String],
+    handle_duplicates: &str,
+) -> MoveOutcome {
+    let mut outcome = MoveOutcome::default();
 
-        let file_info = FileInfo {
-            path: path.to_string_lossy().to_string(),
-            name: file_name.clone(),
-            extension: extension.clone(),
-            size,
-            size_formatted: format_size(size),
-            created_at: get_time(&metadata, true),
-            modified_at: get_time(&metadata, false),
-            is_directory: false,
-            is_hidden: file_name.starts_with('.'),
-            category: category.clone(),
-        };
-
-        // Try custom rules first
-        let mut matched = false;
-        for rule in &enabled_custom_rules {
-            if evaluate_rule(&file_info, rule) {
-                let dest = rule.action_destination.clone().unwrap_or_default();
-                previews.push(UnifiedPreview {
-                    file: file_info.clone(),
-                    match_type: "custom".to_string(),
-                    rule: Some(rule.clone()),
-                    default_rule: None,
-                    action: format_action_preview(rule, &file_info),
-                    destination: dest,
-                });
-                matched = true;
-                break;
-            }
+    for preview in previews {
+        // Pinned files are never moved
+        if preview.match_type == "kept" {
+            continue;
         }
 
-        // If no custom rule matched, try default category rules
-        if !matched {
-            let category_str = format!("{:?}", category).to_lowercase();
-            if let Some(default_rule) = enabled_default_rules.iter().find(|r| r.category == category_str) {
-                let dest_path = source.join(&default_rule.destination);
-                previews.push(UnifiedPreview {
-                    file: file_info.clone(),
-                    match_type: "default".to_string(),
-                    rule: None,
-                    default_rule: Some(default_rule.clone()),
-                    action: format!("이동: {} → {}", file_info.name, default_rule.destination),
-                    destination: dest_path.to_string_lossy().to_string(),
+        // Downloads and files still being written are left for a later run
+        if preview.match_type == "deferred" {
+            outcome.files_deferred += 1;
+            if let Some(reason) = preview.defer_reason {
+                outcome.deferred.push(DeferredFile {
+                    path: preview.file.path.clone(),
+                    reason,
                 });
             }
+            continue;
         }
-    }
-
-    Ok(previews)
-}
-
-/// Execute unified organization
-#[tauri::command]
-pub fn execute_unified(
-    db_state: State<DbPath>,
-    source_path: String,
-    excluded_destinations: Option<Vec<String>>,
-) -> Result<UnifiedOrganizeResult, String> {
-    let db_path = db_state.0.clone();
-    let previews = preview_unified_internal(&db_path, &source_path)?;
-    let excluded = excluded_destinations.unwrap_or_default();
-
-    let mut files_moved = 0;
-    let mut files_skipped = 0;
-    let mut errors: Vec<String> = Vec::new();
-    let mut move_details: Vec<(String, String)> = Vec::new();
-
-    let source = PathBuf::from(&source_path);
 
-    for preview in previews {
         // Skip files in excluded destinations
         if excluded.contains(&preview.destination) {
-            files_skipped += 1;
+            outcome.files_skipped += 1;
             continue;
         }
         let source_file = PathBuf::from(&preview.file.path);
 
-        let dest_folder = if preview.match_type == "custom" {
-            if let Some(rule) = &preview.rule {
-                let mut dest = PathBuf::from(rule.action_destination.clone().unwrap_or_default());
-                if rule.create_date_subfolder && preview.file.modified_at.len() >= 7 {
-                    dest = dest.join(&preview.file.modified_at[..7]);
-                }
-                dest
-            } else {
-                continue;
-            }
-        } else {
-            if let Some(default_rule) = &preview.default_rule {
-                let mut dest = source.join(&default_rule.destination);
-                if default_rule.create_date_subfolder && preview.file.modified_at.len() >= 7 {
-                    dest = dest.join(&preview.file.modified_at[..7]);
-                }
-                dest
-            } else {
-                continue;
-            }
-        };
+        let mut dest_folder = PathBuf::from(&preview.destination);
+        if let Some(date_folder) = &preview.date_folder {
+            dest_folder = dest_folder.join(date_folder);
+        }
 
         // Create destination folder
         if let Err(e) = fs::create_dir_all(&dest_folder) {
-            errors.push(format!("폴더 생성 실패 {}: {}", dest_folder.display(), e));
-            files_skipped += 1;
+            outcome
+                .errors
+                .push(format!("폴더 생성 실패 {}: {}", dest_folder.display(), e));
+            outcome.files_skipped += 1;
             continue;
         }
 
-        let dest_file = dest_folder.join(&preview.file.name);
+        let mut dest_file = dest_folder.join(&preview.file.name);
+        if dest_file.exists() {
+            match handle_duplicates {
+                "skip" => {
+                    outcome.files_skipped += 1;
+                    continue;
+                }
+                "rename" => dest_file = get_unique_path(&dest_file),
+                _ if dest_file.is_file() => {
+                    match quarantine::remove_file(db_path, &dest_file, "통합 정리 덮어쓰기") {
+                        Ok(Removal::Trashed(deleted_at)) => outcome
+                            .trashed
+                            .push((dest_file.to_string_lossy().to_string(), deleted_at)),
+                        Ok(Removal::Quarantined(id)) => outcome.quarantined.push(id),
+                        Err(e) => {
+                            outcome.errors.push(format!(
+                                "덮어쓸 파일 제거 실패 {}: {}",
+                                dest_file.display(),
+                                e
+                            ));
+                            outcome.files_skipped += 1;
+                            continue;
+                        }
+                    }
+                }
+                // Never replace a folder with a file
+                _ => {
+                    outcome.files_skipped += 1;
+                    continue;
+                }
+            }
+        }
 
         // Move file
-        match fs::rename(&source_file, &dest_file) {
+        match mover::move_file(&source_file, &dest_file) {
             Ok(_) => {
-                move_details.push((
+                outcome.move_details.push((
                     preview.file.path.clone(),
                     dest_file.to_string_lossy().to_string(),
                 ));
-                files_moved += 1;
+                outcome.files_moved += 1;
             }
-            Err(_) => {
-                // Try copy + delete for cross-device moves
-                match fs::copy(&source_file, &dest_file) {
-                    Ok(_) => {
-                        if let Err(e) = fs::remove_file(&source_file) {
-                            errors.push(format!("원본 삭제 실패 {}: {}", preview.file.name, e));
-                        }
-                        move_details.push((
-                            preview.file.path.clone(),
-                            dest_file.to_string_lossy().to_string(),
-                        ));
-                        files_moved += 1;
-                    }
-                    Err(e) => {
-                        errors.push(format!("파일 이동 실패 {}: {}", preview.file.name, e));
-                        files_skipped += 1;
-                    }
-                }
+            Err(e) => {
+                outcome
+                    .errors
+                    .push(format!("파일 이동 실패 {}: {}", preview.file.name, e));
+                outcome.files_skipped += 1;
             }
         }
     }
 
-    // Record history
-    let details_json = serde_json::to_string(&move_details).unwrap_or_default();
-    let history_id = crate::database::add_history(
-        &db_path,
-        "organize",
-        &format!("통합 정리: {}개 파일 이동", files_moved),
-        &details_json,
-    )
-    .unwrap_or(-1);
-
-    Ok(UnifiedOrganizeResult {
-        success: errors.is_empty(),
-        files_moved,
-        files_skipped,
-        errors,
-        history_id,
-    })
+    outcome
 }
 
 /// Internal function to get default rules without State wrapper
-fn get_default_rules_internal(db_path: &PathBuf) -> Result<Vec<DefaultRule>, String> {
+pub(crate) fn get_default_rules_internal(db_path: &PathBuf) -> Result<Vec<DefaultRule>, String> {
     let conn = rusqlite::Connection::open(db_path).map_err(|e| e.to_string())?;
 
     // Ensure table exists
@@ -948,7 +1097,8 @@ fn get_default_rules_internal(db_path: &PathBuf) -> Result<Vec<DefaultRule>, Str
             enabled INTEGER NOT NULL DEFAULT 1,
             destination TEXT NOT NULL,
             create_date_subfolder INTEGER DEFAULT 0,
-            priority INTEGER DEFAULT 0
+            priority INTEGER DEFAULT 0,
+            date_source TEXT DEFAULT 'modified'
         )",
         [],
     )
@@ -956,9 +1106,10 @@ fn get_default_rules_internal(db_path: &PathBuf) -> Result<Vec<DefaultRule>, Str
 
     // Add priority column if it doesn't exist (migration for existing DBs)
     let _ = conn.execute("ALTER TABLE default_rules ADD COLUMN priority INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE default_rules ADD COLUMN date_source TEXT DEFAULT 'modified'", []);
 
     let mut stmt = conn
-        .prepare("SELECT id, category, enabled, destination, create_date_subfolder, COALESCE(priority, 0) FROM default_rules ORDER BY priority ASC")
+        .prepare("SELECT id, category, enabled, destination, create_date_subfolder, COALESCE(priority, 0), COALESCE(date_source, 'modified') FROM default_rules ORDER BY priority ASC")
         .map_err(|e| e.to_string())?;
 
     let rules: Vec<DefaultRule> = stmt
@@ -970,6 +1121,7 @@ fn get_default_rules_internal(db_path: &PathBuf) -> Result<Vec<DefaultRule>, Str
                 destination: row.get(3)?,
                 create_date_subfolder: row.get::<_, i32>(4)? != 0,
                 priority: row.get(5)?,
+                date_source: DateSource::parse(&row.get::<_, String>(6)?),
             })
         })
         .map_err(|e| e.to_string())?
@@ -1164,20 +1316,45 @@ fn preview_unified_internal(db_path: &PathBuf, source_path: &str) -> Result<Vec<
     let default_rules = get_default_rules_internal(db_path)?;
     let enabled_default_rules: Vec<DefaultRule> = default_rules.into_iter().filter(|r| r.enabled).collect();
 
+    preview_unified_with(
+        db_path,
+        source_path,
+        &enabled_custom_rules,
+        &enabled_default_rules,
+        None,
+    )
+}
+
+/// Build a unified preview from explicit rule sets. Default-rule destinations are
+/// joined onto `destination_root`, or onto the source folder when it is None.
+pub(crate) fn preview_unified_with(
+    db_path: &PathBuf,
+    source_path: &str,
+    enabled_custom_rules: &[Rule],
+    enabled_default_rules: &[DefaultRule],
+    destination_root: Option<&Path>,
+) -> Result<Vec<UnifiedPreview>, String> {
+    let pins = PinMatcher::load(db_path);
+    let walk_policy = WalkPolicy::load(db_path);
+
     let source = PathBuf::from(source_path);
     if !source.exists() {
         return Err("경로가 존재하지 않습니다".to_string());
     }
 
+    let deferred = SettleChecker::load(db_path).check_dir(&source);
+
     let mut previews: Vec<UnifiedPreview> = Vec::new();
+    let mut metadata_cache = MetadataCache::new();
     let entries = fs::read_dir(&source).map_err(|e| e.to_string())?;
 
     for entry in entries.flatten() {
         let path = entry.path();
 
-        if path.is_dir() {
+        // Skip folders, links to folders and entries the link policy leaves out
+        let Some(metadata) = walk_policy.file_metadata(&path) else {
             continue;
-        }
+        };
 
         let file_name = path
             .file_name()
@@ -1195,7 +1372,8 @@ fn preview_unified_internal(db_path: &PathBuf, source_path: &str) -> Result<Vec<
             .map(|e| format!(".{}", e.to_lowercase()))
             .unwrap_or_default();
 
-        let metadata = fs::metadata(&path).ok();
+        let link_target = link_target(&path);
+        let metadata = Some(metadata);
         let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
         let category = classify_extension(&extension);
 
@@ -1210,20 +1388,67 @@ fn preview_unified_internal(db_path: &PathBuf, source_path: &str) -> Result<Vec<
             is_directory: false,
             is_hidden: file_name.starts_with('.'),
             category: category.clone(),
+            is_symlink: link_target.is_some(),
+            link_target,
         };
 
+        // Pinned files are kept in place
+        if pins.is_pinned(&path) {
+            previews.push(UnifiedPreview {
+                file: file_info.clone(),
+                match_type: "kept".to_string(),
+                rule: None,
+                default_rule: None,
+                action: format!("유지: {} (고정됨)", file_info.name),
+                destination: source.to_string_lossy().to_string(),
+                date_folder: None,
+                date_source: None,
+                defer_reason: None,
+            });
+            continue;
+        }
+
+        if let Some(reason) = deferred.get(&path) {
+            previews.push(UnifiedPreview {
+                file: file_info.clone(),
+                match_type: "deferred".to_string(),
+                rule: None,
+                default_rule: None,
+                action: format!("보류: {} ({})", file_info.name, reason.korean_label()),
+                destination: source.to_string_lossy().to_string(),
+                date_folder: None,
+                date_source: None,
+                defer_reason: Some(*reason),
+            });
+            continue;
+        }
+
         // Try custom rules first
         let mut matched = false;
-        for rule in &enabled_custom_rules {
+        for rule in enabled_custom_rules {
             if evaluate_rule(&file_info, rule) {
-                let dest = rule.action_destination.clone().unwrap_or_default();
+                let dest = render_destination(
+                    rule.action_destination.as_deref().unwrap_or_default(),
+                    &path,
+                    rule.date_source,
+                    &mut metadata_cache,
+                );
+                let (date_folder, date_source) = if rule.create_date_subfolder {
+                    let (folder, source) = rule_date_folder(&file_info.path, rule.date_source);
+                    (Some(folder), source)
+                } else {
+                    (None, None)
+                };
                 previews.push(UnifiedPreview {
                     file: file_info.clone(),
                     match_type: "custom".to_string(),
                     rule: Some(rule.clone()),
                     default_rule: None,
-                    action: format_action_preview(rule, &file_info),
+                    action: format_action_preview(rule, &file_info, &mut metadata_cache),
                     destination: dest,
+                    date_folder,
+                    date_source,
+                    defer_reason: None,
                 });
                 matched = true;
                 break;
@@ -1234,14 +1459,30 @@ fn preview_unified_internal(db_path: &PathBuf, source_path: &str) -> Result<Vec<
         if !matched {
             let category_str = format!("{:?}", category).to_lowercase();
             if let Some(default_rule) = enabled_default_rules.iter().find(|r| r.category == category_str) {
-                let dest_path = source.join(&default_rule.destination);
+                let destination = render_destination(
+                    &default_rule.destination,
+                    &path,
+                    default_rule.date_source,
+                    &mut metadata_cache,
+                );
+                let dest_path = destination_root.unwrap_or(&source).join(&destination);
+                let (date_folder, date_source) = if default_rule.create_date_subfolder {
+                    let (folder, source) =
+                        rule_date_folder(&file_info.path, default_rule.date_source);
+                    (Some(folder), source)
+                } else {
+                    (None, None)
+                };
                 previews.push(UnifiedPreview {
                     file: file_info.clone(),
                     match_type: "default".to_string(),
                     rule: None,
                     default_rule: Some(default_rule.clone()),
-                    action: format!("이동: {} → {}", file_info.name, default_rule.destination),
+                    action: format!("이동: {} → {}", file_info.name, destination),
                     destination: dest_path.to_string_lossy().to_string(),
+                    date_folder,
+                    date_source,
+                    defer_reason: None,
                 });
             }
         }
@@ -1249,3 +1490,54 @@ fn preview_unified_internal(db_path: &PathBuf, source_path: &str) -> Result<Vec<
 
     Ok(previews)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::scanner::get_file_info_internal;
+    use tempfile::tempdir;
+
+    fn move_rule(destination: &Path) -> Rule {
+        Rule {
+            id: None,
+            name: "move".to_string(),
+            priority: 0,
+            enabled: true,
+            conditions: Vec::new(),
+            condition_logic: "AND".to_string(),
+            action_type: "move".to_string(),
+            action_destination: Some(destination.to_string_lossy().to_string()),
+            action_rename_pattern: None,
+            create_date_subfolder: false,
+            date_source: DateSource::Modified,
+        }
+    }
+
+    #[test]
+    fn test_move_action_keeps_existing_destination_file() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(&dest).unwrap();
+        let source = dir.path().join("report.txt");
+        fs::write(&source, b"new").unwrap();
+        fs::write(dest.join("report.txt"), b"old").unwrap();
+
+        let file = get_file_info_internal(&source, &WalkPolicy::load(&db_path)).unwrap();
+        let outcome = execute_action(
+            &db_path,
+            &move_rule(&dest),
+            &file,
+            &mut MetadataCache::new(),
+        )
+        .unwrap();
+
+        let ActionOutcome::Moved(new_path) = outcome else {
+            panic!("file was not moved");
+        };
+        assert_eq!(PathBuf::from(&new_path), dest.join("report (1).txt"));
+        assert_eq!(fs::read(&new_path).unwrap(), b"new");
+        assert_eq!(fs::read(dest.join("report.txt")).unwrap(), b"old");
+        assert!(!source.exists());
+    }
+}
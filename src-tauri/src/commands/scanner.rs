@@ -2,8 +2,12 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
+use tauri::State;
 
+use crate::database::DbPath;
 use crate::services::classifier::{classify_extension, FileCategory};
+use crate::services::filename::to_nfc;
+use crate::services::link_policy::{link_target, WalkPolicy};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +22,10 @@ pub struct FileInfo {
     pub is_directory: bool,
     pub is_hidden: bool,
     pub category: FileCategory,
+    #[serde(default)]
+    pub is_symlink: bool,
+    #[serde(default)]
+    pub link_target: Option<String>,
 }
 
 fn format_size(bytes: u64) -> String {
@@ -62,8 +70,11 @@ fn is_hidden(path: &std::path::Path) -> bool {
     }
 }
 
-fn get_file_info_internal(path: &std::path::Path) -> Result<FileInfo, String> {
-    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+pub(crate) fn get_file_info_internal(path: &std::path::Path, policy: &WalkPolicy) -> Result<FileInfo, String> {
+    let metadata = policy
+        .metadata(path)
+        .ok_or_else(|| format!("Skipped by link policy: {}", path.display()))?;
+    let link_target = link_target(path);
 
     let name = path
         .file_name()
@@ -100,6 +111,8 @@ fn get_file_info_internal(path: &std::path::Path) -> Result<FileInfo, String> {
         is_directory: metadata.is_dir(),
         is_hidden: is_hidden(path),
         category,
+        is_symlink: link_target.is_some(),
+        link_target,
     })
 }
 
@@ -236,9 +249,9 @@ pub fn get_drives() -> Result<Vec<DriveInfo>, String> {
 }
 
 #[tauri::command]
-pub fn scan_desktop() -> Result<Vec<FileInfo>, String> {
+pub fn scan_desktop(db_path: State<DbPath>) -> Result<Vec<FileInfo>, String> {
     let desktop_path = get_desktop_path()?;
-    scan_directory(desktop_path, false, false)
+    scan_directory(desktop_path, false, false, db_path)
 }
 
 #[tauri::command]
@@ -246,8 +259,10 @@ pub fn scan_directory(
     path: String,
     recursive: bool,
     include_hidden: bool,
+    db_path: State<DbPath>,
 ) -> Result<Vec<FileInfo>, String> {
     let dir_path = PathBuf::from(&path);
+    let policy = WalkPolicy::load(&db_path.0);
 
     if !dir_path.exists() {
         return Err(format!("Directory does not exist: {}", path));
@@ -260,10 +275,12 @@ pub fn scan_directory(
     let mut files = Vec::new();
 
     if recursive {
-        for entry in walkdir::WalkDir::new(&dir_path)
+        for entry in policy
+            .walk_dir(&dir_path)
             .min_depth(1)
             .into_iter()
             .filter_map(|e| e.ok())
+            .filter(|e| policy.admits(e))
         {
             let entry_path = entry.path();
 
@@ -271,7 +288,7 @@ pub fn scan_directory(
                 continue;
             }
 
-            if let Ok(info) = get_file_info_internal(entry_path) {
+            if let Ok(info) = get_file_info_internal(entry_path, &policy) {
                 files.push(info);
             }
         }
@@ -285,29 +302,31 @@ pub fn scan_directory(
                 continue;
             }
 
-            if let Ok(info) = get_file_info_internal(&entry_path) {
+            if let Ok(info) = get_file_info_internal(&entry_path, &policy) {
                 files.push(info);
             }
         }
     }
 
     // Sort by name
-    files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    // NFC keys so decomposed (macOS) Hangul names sort with composed ones
+    files.sort_by_cached_key(|f| to_nfc(&f.name).to_lowercase());
 
     Ok(files)
 }
 
 #[tauri::command]
-pub fn get_file_info(path: String) -> Result<FileInfo, String> {
+pub fn get_file_info(path: String, db_path: State<DbPath>) -> Result<FileInfo, String> {
     let file_path = PathBuf::from(&path);
-    get_file_info_internal(&file_path)
+    get_file_info_internal(&file_path, &WalkPolicy::load(&db_path.0))
 }
 
 /// Fast directory listing - only reads immediate children (depth=0)
 /// Uses OS-specific optimizations where possible
 #[tauri::command]
-pub fn fast_list_directory(path: String) -> Result<Vec<FileInfo>, String> {
+pub fn fast_list_directory(path: String, db_path: State<DbPath>) -> Result<Vec<FileInfo>, String> {
     let dir_path = PathBuf::from(&path);
+    let policy = WalkPolicy::load(&db_path.0);
 
     println!("[fast_list_directory] path: {}", path);
 
@@ -341,7 +360,7 @@ pub fn fast_list_directory(path: String) -> Result<Vec<FileInfo>, String> {
         }
 
         // Get basic info without deep metadata collection
-        if let Ok(info) = get_file_info_fast(&entry_path) {
+        if let Ok(info) = get_file_info_fast(&entry_path, &policy) {
             files.push(info);
         }
     }
@@ -353,7 +372,7 @@ pub fn fast_list_directory(path: String) -> Result<Vec<FileInfo>, String> {
         match (a.is_directory, b.is_directory) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            _ => to_nfc(&a.name).to_lowercase().cmp(&to_nfc(&b.name).to_lowercase()),
         }
     });
 
@@ -361,8 +380,11 @@ pub fn fast_list_directory(path: String) -> Result<Vec<FileInfo>, String> {
 }
 
 /// Lightweight file info - minimal metadata for fast listing
-fn get_file_info_fast(path: &std::path::Path) -> Result<FileInfo, String> {
-    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+fn get_file_info_fast(path: &std::path::Path, policy: &WalkPolicy) -> Result<FileInfo, String> {
+    let metadata = policy
+        .metadata(path)
+        .ok_or_else(|| format!("Skipped by link policy: {}", path.display()))?;
+    let link_target = link_target(path);
 
     let name = path
         .file_name()
@@ -397,5 +419,7 @@ fn get_file_info_fast(path: &std::path::Path) -> Result<FileInfo, String> {
         is_directory: metadata.is_dir(),
         is_hidden: is_hidden(path),
         category,
+        is_symlink: link_target.is_some(),
+        link_target,
     })
 }
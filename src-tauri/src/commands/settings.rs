@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
 use tauri::State;
 
+use crate::commands::quarantine::DEFAULT_RETENTION_DAYS;
+use crate::commands::renamer::RenamePreset;
 use crate::database::{self, DbPath};
+use crate::services::jobs::DEFAULT_MAX_CONCURRENT_JOBS;
+use crate::services::link_policy::{SpecialFilePolicy, SymlinkPolicy};
+use crate::services::settle::DEFAULT_SETTLE_SECONDS;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +22,16 @@ pub struct AppSettings {
     pub show_hidden_files: bool,
     pub confirm_before_delete: bool,
     pub use_trash: bool,
+    pub settle_seconds: u64,
+    /// Defer files that another app has open or locked
+    pub check_in_use: bool,
+    pub symlink_policy: SymlinkPolicy,
+    pub special_file_policy: SpecialFilePolicy,
+    pub use_quarantine: bool,
+    /// Empty uses the quarantine folder in the app data folder
+    pub quarantine_path: String,
+    pub quarantine_retention_days: u32,
+    pub max_concurrent_jobs: usize,
 }
 
 impl Default for AppSettings {
@@ -33,6 +50,14 @@ impl Default for AppSettings {
             show_hidden_files: false,
             confirm_before_delete: true,
             use_trash: true,
+            settle_seconds: DEFAULT_SETTLE_SECONDS,
+            check_in_use: true,
+            symlink_policy: SymlinkPolicy::default(),
+            special_file_policy: SpecialFilePolicy::default(),
+            use_quarantine: false,
+            quarantine_path: String::new(),
+            quarantine_retention_days: DEFAULT_RETENTION_DAYS,
+            max_concurrent_jobs: DEFAULT_MAX_CONCURRENT_JOBS,
         }
     }
 }
@@ -76,12 +101,44 @@ pub fn get_settings(db_path: State<DbPath>) -> Result<AppSettings, String> {
             .get("use_trash")
             .map(|v| v == "true")
             .unwrap_or(default.use_trash),
+        settle_seconds: settings
+            .get("settle_seconds")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default.settle_seconds),
+        check_in_use: settings
+            .get("check_in_use")
+            .map(|v| v != "false")
+            .unwrap_or(default.check_in_use),
+        symlink_policy: settings
+            .get("symlink_policy")
+            .map(|v| SymlinkPolicy::parse(v))
+            .unwrap_or(default.symlink_policy),
+        special_file_policy: settings
+            .get("special_file_policy")
+            .map(|v| SpecialFilePolicy::parse(v))
+            .unwrap_or(default.special_file_policy),
+        use_quarantine: settings
+            .get("use_quarantine")
+            .map(|v| v == "true")
+            .unwrap_or(default.use_quarantine),
+        quarantine_path: settings
+            .get("quarantine_path")
+            .cloned()
+            .unwrap_or(default.quarantine_path),
+        quarantine_retention_days: settings
+            .get("quarantine_retention_days")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default.quarantine_retention_days),
+        max_concurrent_jobs: settings
+            .get("max_concurrent_jobs")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default.max_concurrent_jobs),
     })
 }
 
 #[tauri::command]
 pub fn update_settings(
-    settings: std::collections::HashMap<String, String>,
+    settings: HashMap<String, String>,
     db_path: State<DbPath>,
 ) -> Result<AppSettings, String> {
     for (key, value) in settings {
@@ -90,3 +147,64 @@ pub fn update_settings(
 
     get_settings(db_path)
 }
+
+/// Settings file written by `export_settings`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsExport {
+    pub version: u32,
+    pub settings: HashMap<String, String>,
+    #[serde(default)]
+    pub rename_presets: Vec<RenamePreset>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsImportResult {
+    pub settings_imported: usize,
+    pub presets_imported: usize,
+}
+
+/// Write all settings and rename presets to a JSON file
+#[tauri::command]
+pub fn export_settings(file_path: String, db_path: State<DbPath>) -> Result<(), String> {
+    let export = SettingsExport {
+        version: 1,
+        settings: database::get_all_settings(&db_path.0)?,
+        rename_presets: database::get_rename_presets(&db_path.0)?
+            .into_iter()
+            .map(|p| RenamePreset {
+                id: None,
+                created_at: None,
+                ..p
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    fs::write(&file_path, json).map_err(|e| format!("설정 내보내기 실패: {}", e))
+}
+
+/// Load settings and rename presets from a file written by `export_settings`.
+/// Presets with the same name as an existing preset replace it.
+#[tauri::command]
+pub fn import_settings(file_path: String, db_path: State<DbPath>) -> Result<SettingsImportResult, String> {
+    let json = fs::read_to_string(&file_path).map_err(|e| format!("설정 파일을 읽을 수 없습니다: {}", e))?;
+    let import: SettingsExport =
+        serde_json::from_str(&json).map_err(|e| format!("올바른 설정 파일이 아닙니다: {}", e))?;
+
+    for (key, value) in &import.settings {
+        database::set_setting(&db_path.0, key, value)?;
+    }
+
+    let existing = database::get_rename_presets(&db_path.0)?;
+    for preset in &import.rename_presets {
+        let id = existing.iter().find(|p| p.name == preset.name).and_then(|p| p.id);
+        database::save_rename_preset(&db_path.0, &RenamePreset { id, ..preset.clone() })?;
+    }
+
+    Ok(SettingsImportResult {
+        settings_imported: import.settings.len(),
+        presets_imported: import.rename_presets.len(),
+    })
+}
@@ -3,6 +3,11 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::commands::history::HistoryItem;
+use crate::commands::jobs::Job;
+use crate::commands::pins::PinnedFile;
+use crate::commands::profiles::OrganizeProfile;
+use crate::commands::quarantine::QuarantinedFile;
+use crate::commands::renamer::RenamePreset;
 
 /// Database path state for Tauri
 pub struct DbPath(pub PathBuf);
@@ -32,6 +37,7 @@ pub fn init_database(db_path: &PathBuf) -> Result<(), String> {
             action_destination TEXT,
             action_rename_pattern TEXT,
             create_date_subfolder INTEGER DEFAULT 0,
+            date_source TEXT DEFAULT 'modified',
             created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
             updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
         );
@@ -64,16 +70,74 @@ pub fn init_database(db_path: &PathBuf) -> Result<(), String> {
             created_at DATETIME DEFAULT CURRENT_TIMESTAMP
         );
 
+        -- Pinned files (never moved by organize, rules or watcher)
+        CREATE TABLE IF NOT EXISTS pinned_files (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pattern TEXT NOT NULL,
+            pin_type TEXT NOT NULL,
+            file_size INTEGER,
+            label TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Organize profiles (several source folders with their own rules)
+        CREATE TABLE IF NOT EXISTS organize_profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            sources TEXT NOT NULL,
+            destination_root TEXT,
+            default_rules TEXT NOT NULL DEFAULT '[]',
+            options TEXT NOT NULL DEFAULT '{}',
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Rename presets (an ordered list of rename rules)
+        CREATE TABLE IF NOT EXISTS rename_presets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            rules TEXT NOT NULL DEFAULT '[]',
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Quarantined files (removed by rules or duplicate cleanup, kept for retention)
+        CREATE TABLE IF NOT EXISTS quarantine (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            original_path TEXT NOT NULL,
+            stored_path TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            size INTEGER DEFAULT 0,
+            quarantined_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Background jobs (queue survives restarts)
+        CREATE TABLE IF NOT EXISTS jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            params TEXT NOT NULL DEFAULT '{}',
+            status TEXT NOT NULL DEFAULT 'queued',
+            paths TEXT NOT NULL DEFAULT '[]',
+            result TEXT,
+            error TEXT,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            started_at DATETIME,
+            finished_at DATETIME
+        );
+
         -- Create indexes
         CREATE INDEX IF NOT EXISTS idx_rules_priority ON rules(priority);
         CREATE INDEX IF NOT EXISTS idx_rules_enabled ON rules(enabled);
         CREATE INDEX IF NOT EXISTS idx_history_created ON history(created_at);
         CREATE INDEX IF NOT EXISTS idx_history_undone ON history(is_undone);
         CREATE INDEX IF NOT EXISTS idx_extension_mappings_ext ON extension_mappings(extension);
+        CREATE INDEX IF NOT EXISTS idx_quarantine_time ON quarantine(quarantined_at);
+        CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
         "#,
     )
     .map_err(|e| e.to_string())?;
 
+    // Add date_source column if it doesn't exist (migration for existing DBs)
+    let _ = conn.execute("ALTER TABLE rules ADD COLUMN date_source TEXT DEFAULT 'modified'", []);
+
     // Insert default extension mappings if table is empty
     let count: i64 = conn
         .query_row(
@@ -321,9 +385,431 @@ pub fn clear_history(db_path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+// Pin functions
+pub fn add_pin(db_path: &PathBuf, pin: &PinnedFile) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO pinned_files (pattern, pin_type, file_size, label) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            pin.pattern,
+            pin.pin_type,
+            pin.file_size.map(|s| s as i64),
+            pin.label,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn get_pins(db_path: &PathBuf) -> Result<Vec<PinnedFile>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, pattern, pin_type, file_size, label, created_at
+             FROM pinned_files
+             ORDER BY created_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(PinnedFile {
+                id: Some(row.get(0)?),
+                pattern: row.get(1)?,
+                pin_type: row.get(2)?,
+                file_size: row.get::<_, Option<i64>>(3)?.map(|s| s as u64),
+                label: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let pins: Vec<_> = rows.flatten().collect();
+
+    Ok(pins)
+}
+
+pub fn remove_pin(db_path: &PathBuf, id: i64) -> Result<(), String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM pinned_files WHERE id = ?1", [id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Profile functions
+pub fn save_profile(db_path: &PathBuf, profile: &OrganizeProfile) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let sources = serde_json::to_string(&profile.sources).map_err(|e| e.to_string())?;
+    let default_rules = serde_json::to_string(&profile.default_rules).map_err(|e| e.to_string())?;
+    let options = serde_json::to_string(&profile.options).map_err(|e| e.to_string())?;
+
+    if let Some(id) = profile.id {
+        conn.execute(
+            "UPDATE organize_profiles SET name = ?1, sources = ?2, destination_root = ?3,
+             default_rules = ?4, options = ?5 WHERE id = ?6",
+            rusqlite::params![
+                profile.name,
+                sources,
+                profile.destination_root,
+                default_rules,
+                options,
+                id,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(id)
+    } else {
+        conn.execute(
+            "INSERT INTO organize_profiles (name, sources, destination_root, default_rules, options)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                profile.name,
+                sources,
+                profile.destination_root,
+                default_rules,
+                options,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(conn.last_insert_rowid())
+    }
+}
+
+fn row_to_profile(row: &rusqlite::Row) -> SqliteResult<OrganizeProfile> {
+    let sources: String = row.get(2)?;
+    let default_rules: String = row.get(4)?;
+    let options: String = row.get(5)?;
+
+    Ok(OrganizeProfile {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        sources: serde_json::from_str(&sources).unwrap_or_default(),
+        destination_root: row.get(3)?,
+        default_rules: serde_json::from_str(&default_rules).unwrap_or_default(),
+        options: serde_json::from_str(&options).unwrap_or_default(),
+        created_at: row.get(6)?,
+    })
+}
+
+pub fn get_profiles(db_path: &PathBuf) -> Result<Vec<OrganizeProfile>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, sources, destination_root, default_rules, options, created_at
+             FROM organize_profiles
+             ORDER BY name ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], row_to_profile)
+        .map_err(|e| e.to_string())?;
+
+    let profiles: Vec<_> = rows.flatten().collect();
+
+    Ok(profiles)
+}
+
+pub fn get_profile(db_path: &PathBuf, id: i64) -> Result<OrganizeProfile, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT id, name, sources, destination_root, default_rules, options, created_at
+         FROM organize_profiles WHERE id = ?1",
+        [id],
+        row_to_profile,
+    )
+    .map_err(|_| "프로필을 찾을 수 없습니다".to_string())
+}
+
+pub fn delete_profile(db_path: &PathBuf, id: i64) -> Result<(), String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM organize_profiles WHERE id = ?1", [id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Rename preset functions
+pub fn save_rename_preset(db_path: &PathBuf, preset: &RenamePreset) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let rules = serde_json::to_string(&preset.rules).map_err(|e| e.to_string())?;
+
+    if let Some(id) = preset.id {
+        conn.execute(
+            "UPDATE rename_presets SET name = ?1, rules = ?2 WHERE id = ?3",
+            rusqlite::params![preset.name, rules, id],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(id)
+    } else {
+        conn.execute(
+            "INSERT INTO rename_presets (name, rules) VALUES (?1, ?2)",
+            rusqlite::params![preset.name, rules],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(conn.last_insert_rowid())
+    }
+}
+
+fn row_to_rename_preset(row: &rusqlite::Row) -> SqliteResult<RenamePreset> {
+    let rules: String = row.get(2)?;
+
+    Ok(RenamePreset {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        rules: serde_json::from_str(&rules).unwrap_or_default(),
+        created_at: row.get(3)?,
+    })
+}
+
+pub fn get_rename_presets(db_path: &PathBuf) -> Result<Vec<RenamePreset>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, name, rules, created_at FROM rename_presets ORDER BY name ASC")
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], row_to_rename_preset)
+        .map_err(|e| e.to_string())?;
+
+    let presets: Vec<_> = rows.flatten().collect();
+
+    Ok(presets)
+}
+
+pub fn get_rename_preset(db_path: &PathBuf, id: i64) -> Result<RenamePreset, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT id, name, rules, created_at FROM rename_presets WHERE id = ?1",
+        [id],
+        row_to_rename_preset,
+    )
+    .map_err(|_| "프리셋을 찾을 수 없습니다".to_string())
+}
+
+pub fn delete_rename_preset(db_path: &PathBuf, id: i64) -> Result<(), String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM rename_presets WHERE id = ?1", [id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Quarantine functions
+pub fn add_quarantined(db_path: &PathBuf, item: &QuarantinedFile) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO quarantine (original_path, stored_path, reason, size) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![item.original_path, item.stored_path, item.reason, item.size as i64],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn row_to_quarantined(row: &rusqlite::Row) -> SqliteResult<QuarantinedFile> {
+    Ok(QuarantinedFile {
+        id: Some(row.get(0)?),
+        original_path: row.get(1)?,
+        stored_path: row.get(2)?,
+        reason: row.get(3)?,
+        size: row.get::<_, i64>(4)? as u64,
+        quarantined_at: row.get(5)?,
+    })
+}
+
+fn query_quarantined(
+    conn: &Connection,
+    filter: &str,
+    params: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<QuarantinedFile>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, original_path, stored_path, reason, size, quarantined_at
+             FROM quarantine {} ORDER BY quarantined_at DESC, id DESC",
+            filter
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params, row_to_quarantined)
+        .map_err(|e| e.to_string())?;
+
+    let items: Vec<_> = rows.flatten().collect();
+
+    Ok(items)
+}
+
+pub fn get_quarantined_files(db_path: &PathBuf) -> Result<Vec<QuarantinedFile>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    query_quarantined(&conn, "", &[])
+}
+
+pub fn get_quarantined_file(db_path: &PathBuf, id: i64) -> Result<QuarantinedFile, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        "SELECT id, original_path, stored_path, reason, size, quarantined_at
+         FROM quarantine WHERE id = ?1",
+        [id],
+        row_to_quarantined,
+    )
+    .map_err(|_| "격리된 파일을 찾을 수 없습니다".to_string())
+}
+
+/// Files quarantined more than `days` days ago
+pub fn get_expired_quarantine(db_path: &PathBuf, days: u32) -> Result<Vec<QuarantinedFile>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    let modifier = format!("-{} days", days);
+    query_quarantined(&conn, "WHERE quarantined_at <= datetime('now', ?1)", &[&modifier])
+}
+
+pub fn delete_quarantined(db_path: &PathBuf, id: i64) -> Result<(), String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM quarantine WHERE id = ?1", [id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Job functions
+pub fn add_job(db_path: &PathBuf, kind: &str, params: &str, paths: &str) -> Result<i64, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT INTO jobs (kind, params, paths) VALUES (?1, ?2, ?3)",
+        [kind, params, paths],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn row_to_job(row: &rusqlite::Row) -> SqliteResult<Job> {
+    let params: String = row.get(2)?;
+    let paths: String = row.get(4)?;
+    let result: Option<String> = row.get(5)?;
+
+    Ok(Job {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        params: serde_json::from_str(&params).unwrap_or_default(),
+        status: row.get(3)?,
+        paths: serde_json::from_str(&paths).unwrap_or_default(),
+        result: result.and_then(|r| serde_json::from_str(&r).ok()),
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        started_at: row.get(8)?,
+        finished_at: row.get(9)?,
+    })
+}
+
+fn query_jobs(
+    conn: &Connection,
+    filter: &str,
+    order: &str,
+    params: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<Job>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, kind, params, status, paths, result, error, created_at, started_at, finished_at
+             FROM jobs {} ORDER BY {}",
+            filter, order
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt.query_map(params, row_to_job).map_err(|e| e.to_string())?;
+
+    let jobs: Vec<_> = rows.flatten().collect();
+
+    Ok(jobs)
+}
+
+/// All jobs, newest first
+pub fn get_jobs(db_path: &PathBuf) -> Result<Vec<Job>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    query_jobs(&conn, "", "id DESC", &[])
+}
+
+/// Jobs with the given status in queue order
+pub fn get_jobs_by_status(db_path: &PathBuf, status: &str) -> Result<Vec<Job>, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    query_jobs(&conn, "WHERE status = ?1", "id ASC", &[&status])
+}
+
+pub fn get_job(db_path: &PathBuf, id: i64) -> Result<Job, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+    query_jobs(&conn, "WHERE id = ?1", "id", &[&id])?
+        .pop()
+        .ok_or_else(|| "작업을 찾을 수 없습니다".to_string())
+}
+
+/// Change the status of an unfinished job; the first switch to running records the start time.
+/// Returns false when the job had already finished (or been cancelled) and was left alone.
+pub fn set_job_status(db_path: &PathBuf, id: i64, status: &str) -> Result<bool, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    let updated = conn.execute(
+        "UPDATE jobs SET status = ?2,
+             started_at = CASE WHEN ?2 = 'running' THEN COALESCE(started_at, CURRENT_TIMESTAMP)
+                          ELSE started_at END
+         WHERE id = ?1 AND status IN ('queued', 'running', 'paused')",
+        rusqlite::params![id, status],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(updated == 1)
+}
+
+pub fn finish_job(
+    db_path: &PathBuf,
+    id: i64,
+    status: &str,
+    result: Option<&str>,
+    error: Option<&str>,
+) -> Result<(), String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE jobs SET status = ?2, result = ?3, error = ?4, finished_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        rusqlite::params![id, status, result, error],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Put jobs that were running when the app quit back in the queue; returns how many
+pub fn requeue_interrupted_jobs(db_path: &PathBuf) -> Result<usize, String> {
+    let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE jobs SET status = 'queued', started_at = NULL WHERE status = 'running'",
+        [],
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::renamer::RenameRule;
     use std::fs;
     use tempfile::tempdir;
 
@@ -354,6 +840,9 @@ mod tests {
         assert!(tables.contains(&"extension_mappings".to_string()));
         assert!(tables.contains(&"exclusions".to_string()));
         assert!(tables.contains(&"history".to_string()));
+        assert!(tables.contains(&"pinned_files".to_string()));
+        assert!(tables.contains(&"organize_profiles".to_string()));
+        assert!(tables.contains(&"rename_presets".to_string()));
     }
 
     #[test]
@@ -521,4 +1010,160 @@ mod tests {
         let history = get_history(&db_path, 10, 10).unwrap();
         assert_eq!(history.len(), 0);
     }
+
+    #[test]
+    fn test_add_get_remove_pins() {
+        let (db_path, _dir) = create_test_db();
+
+        let pin = PinnedFile {
+            id: None,
+            pattern: "/Users/me/Desktop/todo.txt".to_string(),
+            pin_type: "path".to_string(),
+            file_size: None,
+            label: Some("todo.txt".to_string()),
+            created_at: None,
+        };
+        let id = add_pin(&db_path, &pin).unwrap();
+        add_pin(
+            &db_path,
+            &PinnedFile {
+                pattern: "abc123".to_string(),
+                pin_type: "hash".to_string(),
+                file_size: Some(42),
+                ..pin.clone()
+            },
+        )
+        .unwrap();
+
+        let pins = get_pins(&db_path).unwrap();
+        assert_eq!(pins.len(), 2);
+        assert!(pins.iter().any(|p| p.pin_type == "hash" && p.file_size == Some(42)));
+
+        remove_pin(&db_path, id).unwrap();
+        let pins = get_pins(&db_path).unwrap();
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].pin_type, "hash");
+    }
+
+    #[test]
+    fn test_save_get_delete_profile() {
+        let (db_path, _dir) = create_test_db();
+
+        let mut profile = OrganizeProfile {
+            id: None,
+            name: "Downloads".to_string(),
+            sources: vec!["~/Downloads".to_string(), "/tmp/inbox".to_string()],
+            destination_root: Some("~/Pictures/Inbox".to_string()),
+            default_rules: Vec::new(),
+            options: Default::default(),
+            created_at: None,
+        };
+        let id = save_profile(&db_path, &profile).unwrap();
+
+        let loaded = get_profile(&db_path, id).unwrap();
+        assert_eq!(loaded.sources.len(), 2);
+        assert_eq!(loaded.destination_root.as_deref(), Some("~/Pictures/Inbox"));
+        assert!(loaded.options.include_custom_rules);
+
+        profile.id = Some(id);
+        profile.options.handle_duplicates = "skip".to_string();
+        save_profile(&db_path, &profile).unwrap();
+        let profiles = get_profiles(&db_path).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].options.handle_duplicates, "skip");
+
+        delete_profile(&db_path, id).unwrap();
+        assert!(get_profiles(&db_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_get_delete_rename_preset() {
+        let (db_path, _dir) = create_test_db();
+
+        let rule: RenameRule = serde_json::from_value(serde_json::json!({
+            "ruleType": "template",
+            "template": "{date:%Y-%m-%d}_{name:slug}{ext}"
+        }))
+        .unwrap();
+        let mut preset = RenamePreset {
+            id: None,
+            name: "Photos".to_string(),
+            rules: vec![rule],
+            created_at: None,
+        };
+        let id = save_rename_preset(&db_path, &preset).unwrap();
+
+        let loaded = get_rename_preset(&db_path, id).unwrap();
+        assert_eq!(loaded.rules.len(), 1);
+        assert_eq!(loaded.rules[0].rule_type, "template");
+
+        preset.id = Some(id);
+        preset.name = "Camera".to_string();
+        save_rename_preset(&db_path, &preset).unwrap();
+        let presets = get_rename_presets(&db_path).unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "Camera");
+
+        delete_rename_preset(&db_path, id).unwrap();
+        assert!(get_rename_presets(&db_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_quarantine_index_and_expiry() {
+        let (db_path, _dir) = create_test_db();
+
+        let item = QuarantinedFile {
+            id: None,
+            original_path: "/home/user/Desktop/old.log".to_string(),
+            stored_path: "/data/quarantine/20240101_000000/old.log".to_string(),
+            reason: "규칙: 로그 정리".to_string(),
+            size: 42,
+            quarantined_at: None,
+        };
+        let old_id = add_quarantined(&db_path, &item).unwrap();
+        let new_id = add_quarantined(&db_path, &item).unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "UPDATE quarantine SET quarantined_at = datetime('now', '-40 days') WHERE id = ?1",
+            [old_id],
+        )
+        .unwrap();
+
+        let expired = get_expired_quarantine(&db_path, 30).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, Some(old_id));
+        assert_eq!(get_quarantined_file(&db_path, new_id).unwrap().size, 42);
+
+        delete_quarantined(&db_path, old_id).unwrap();
+        assert_eq!(get_quarantined_files(&db_path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_job_queue_persistence() {
+        let (db_path, _dir) = create_test_db();
+
+        let first = add_job(&db_path, "dedupe", r#"{"path":"/tmp/a"}"#, r#"["/tmp/a"]"#).unwrap();
+        let second = add_job(&db_path, "dedupe", r#"{"path":"/tmp/b"}"#, r#"["/tmp/b"]"#).unwrap();
+
+        assert!(set_job_status(&db_path, first, "running").unwrap());
+        let job = get_job(&db_path, first).unwrap();
+        assert_eq!(job.status, "running");
+        assert!(job.started_at.is_some());
+        assert_eq!(job.paths, vec!["/tmp/a".to_string()]);
+        assert_eq!(job.params["path"], "/tmp/a");
+
+        // An interrupted job goes back to the queue ahead of later jobs
+        assert_eq!(requeue_interrupted_jobs(&db_path).unwrap(), 1);
+        let queued = get_jobs_by_status(&db_path, "queued").unwrap();
+        assert_eq!(queued.iter().map(|j| j.id).collect::<Vec<_>>(), vec![first, second]);
+
+        finish_job(&db_path, second, "completed", Some(r#"{"filesRemoved":2}"#), None).unwrap();
+        // A finished job keeps its status
+        assert!(!set_job_status(&db_path, second, "paused").unwrap());
+        let job = get_job(&db_path, second).unwrap();
+        assert_eq!(job.status, "completed");
+        assert_eq!(job.result.unwrap()["filesRemoved"], 2);
+        assert_eq!(get_jobs(&db_path).unwrap()[0].id, second);
+    }
 }
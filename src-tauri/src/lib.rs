@@ -24,8 +24,15 @@ pub fn run() {
             let db_path = app_data_dir.join("data.db");
             database::init_database(&db_path)?;
 
+            // Purge quarantined files past their retention period
+            let _ = commands::quarantine::purge_expired(&db_path);
+
             // Store database path in app state
-            app.manage(database::DbPath(db_path));
+            app.manage(database::DbPath(db_path.clone()));
+
+            // Start the background job queue, resuming jobs left from the last run
+            let jobs = commands::jobs::JobManager::start(db_path, app.handle().clone());
+            app.manage(commands::jobs::JobState(jobs));
 
             // Initialize file watcher state
             app.manage(WatcherState::new());
@@ -101,8 +108,14 @@ pub fn run() {
             commands::file_ops::move_file,
             commands::file_ops::copy_file,
             commands::file_ops::delete_file,
+            commands::file_ops::batch_move,
+            commands::file_ops::batch_copy,
+            commands::file_ops::batch_delete,
             commands::file_ops::rename_file,
             commands::file_ops::create_folder,
+            // Archive commands
+            commands::archive::extract_archive,
+            commands::archive::create_archive,
             // Backup commands
             commands::file_ops::backup_desktop,
             commands::file_ops::list_backups,
@@ -111,12 +124,42 @@ pub fn run() {
             // Organizer commands
             commands::organizer::preview_organization,
             commands::organizer::execute_organization,
+            commands::organizer::preview_flatten,
+            commands::organizer::flatten_folder,
+            // Pin commands
+            commands::pins::get_pins,
+            commands::pins::add_pin,
+            commands::pins::remove_pin,
+            // Quarantine commands
+            commands::quarantine::get_quarantined_files,
+            commands::quarantine::restore_quarantined,
+            commands::quarantine::purge_quarantined,
+            commands::quarantine::purge_expired_quarantine,
+            // Job commands
+            commands::jobs::enqueue_job,
+            commands::jobs::list_jobs,
+            commands::jobs::pause_job,
+            commands::jobs::resume_job,
+            commands::jobs::cancel_job,
+            // Profile commands
+            commands::profiles::get_profiles,
+            commands::profiles::save_profile,
+            commands::profiles::delete_profile,
+            commands::profiles::preview_profile,
+            commands::profiles::execute_profile,
             // Renamer commands
             commands::renamer::preview_rename,
             commands::renamer::execute_rename,
+            commands::renamer::get_rename_presets,
+            commands::renamer::save_rename_preset,
+            commands::renamer::delete_rename_preset,
+            commands::renamer::preview_rename_preset,
+            commands::renamer::execute_rename_preset,
             // Settings
             commands::settings::get_settings,
             commands::settings::update_settings,
+            commands::settings::export_settings,
+            commands::settings::import_settings,
             // History
             commands::history::get_history,
             commands::history::undo_operation,
@@ -0,0 +1,636 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use filetime::FileTime;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Largest total size an archive may extract to
+pub const MAX_EXTRACT_SIZE: u64 = 20 * 1024 * 1024 * 1024;
+/// Most entries an archive may hold
+pub const MAX_EXTRACT_ENTRIES: usize = 100_000;
+/// Largest ratio of extracted size to archive size, checked past `RATIO_CHECK_FLOOR`
+pub const MAX_COMPRESSION_RATIO: u64 = 200;
+const RATIO_CHECK_FLOOR: u64 = 64 * 1024 * 1024;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarZst,
+}
+
+impl ArchiveFormat {
+    /// Name suffixes for each format, longest first so ".tar.gz" wins over ".gz"
+    const SUFFIXES: &'static [(&'static str, ArchiveFormat)] = &[
+        (".tar.gz", ArchiveFormat::TarGz),
+        (".tar.zst", ArchiveFormat::TarZst),
+        (".tgz", ArchiveFormat::TarGz),
+        (".tzst", ArchiveFormat::TarZst),
+        (".tar", ArchiveFormat::Tar),
+        (".zip", ArchiveFormat::Zip),
+    ];
+
+    /// Detect the format from the file name
+    pub fn detect(path: &Path) -> Option<Self> {
+        split_name(path).map(|(_, _, format)| format)
+    }
+}
+
+/// Split an archive file name into the name without the format suffix and the suffix
+fn split_name(path: &Path) -> Option<(String, String, ArchiveFormat)> {
+    let name = path.file_name()?.to_str()?;
+    let lower = name.to_lowercase();
+
+    ArchiveFormat::SUFFIXES.iter().find_map(|(suffix, format)| {
+        let stem_len = name.len().checked_sub(suffix.len())?;
+        (lower.ends_with(suffix) && stem_len > 0)
+            .then(|| (name[..stem_len].to_string(), name[stem_len..].to_string(), *format))
+    })
+}
+
+/// The folder an archive extracts into: its name without the suffix, next to the archive,
+/// numbered when that name is taken
+pub fn extract_dir_for(archive: &Path) -> Option<PathBuf> {
+    let (stem, _, _) = split_name(archive)?;
+    let parent = archive.parent()?;
+
+    let mut dir = parent.join(&stem);
+    let mut counter = 1;
+    while fs::symlink_metadata(&dir).is_ok() {
+        dir = parent.join(format!("{}_{}", stem, counter));
+        counter += 1;
+    }
+    Some(dir)
+}
+
+/// A free archive path, numbering the name before the whole suffix ("photos_1.tar.gz")
+pub fn unique_archive_path(path: &Path) -> PathBuf {
+    let Some((stem, suffix, _)) = split_name(path) else {
+        return path.to_path_buf();
+    };
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut candidate = path.to_path_buf();
+    let mut counter = 1;
+    while fs::symlink_metadata(&candidate).is_ok() {
+        candidate = parent.join(format!("{}_{}{}", stem, counter, suffix));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Limits that stop archives which expand far beyond their size (zip bombs)
+pub struct ExtractLimits {
+    pub max_size: u64,
+    pub max_entries: usize,
+    pub max_ratio: u64,
+}
+
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        ExtractLimits {
+            max_size: MAX_EXTRACT_SIZE,
+            max_entries: MAX_EXTRACT_ENTRIES,
+            max_ratio: MAX_COMPRESSION_RATIO,
+        }
+    }
+}
+
+/// What an extraction wrote, for the result and for undo
+#[derive(Debug, Default)]
+pub struct Extracted {
+    pub files: Vec<PathBuf>,
+    /// Folders created, parents before children; the first is the extraction folder
+    pub created_dirs: Vec<PathBuf>,
+    pub total_size: u64,
+    /// Entries left out (links, special files, unsafe paths), one message each
+    pub skipped: Vec<String>,
+}
+
+/// Extract an archive into `dest`, which must not exist yet. Entries that would land
+/// outside `dest` and links are skipped; if a limit is exceeded or the archive is broken,
+/// everything written so far is removed again.
+pub fn extract(archive: &Path, dest: &Path, limits: &ExtractLimits) -> Result<Extracted, String> {
+    let format = ArchiveFormat::detect(archive).ok_or("지원하지 않는 압축 형식입니다")?;
+    let archive_size = fs::metadata(archive).map_err(|e| e.to_string())?.len();
+
+    fs::create_dir(dest).map_err(|e| format!("압축 해제 폴더를 만들 수 없습니다: {}", e))?;
+
+    let mut extractor = Extractor {
+        dest,
+        limits,
+        archive_size,
+        entries: 0,
+        extracted: Extracted {
+            created_dirs: vec![dest.to_path_buf()],
+            ..Extracted::default()
+        },
+    };
+
+    let result = File::open(archive)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            let reader = BufReader::new(file);
+            match format {
+                ArchiveFormat::Zip => extractor.zip(reader),
+                ArchiveFormat::Tar => extractor.tar(reader),
+                ArchiveFormat::TarGz => extractor.tar(GzDecoder::new(reader)),
+                ArchiveFormat::TarZst => {
+                    extractor.tar(zstd::Decoder::with_buffer(reader).map_err(|e| e.to_string())?)
+                }
+            }
+        });
+
+    match result {
+        Ok(()) => Ok(extractor.extracted),
+        Err(e) => {
+            extractor.rollback();
+            Err(e)
+        }
+    }
+}
+
+struct Extractor<'a> {
+    dest: &'a Path,
+    limits: &'a ExtractLimits,
+    archive_size: u64,
+    entries: usize,
+    extracted: Extracted,
+}
+
+impl Extractor<'_> {
+    fn zip<R: Read + io::Seek>(&mut self, reader: R) -> Result<(), String> {
+        let mut archive =
+            ZipArchive::new(reader).map_err(|e| format!("압축 파일을 읽을 수 없습니다: {}", e))?;
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+            self.count_entry()?;
+
+            let name = entry.name().to_string();
+            // enclosed_name rejects absolute paths and ".." (zip slip)
+            let Some(path) = entry.enclosed_name().and_then(|p| self.entry_path(&p)) else {
+                self.skip(&name, "안전하지 않은 경로");
+                continue;
+            };
+
+            if entry.is_dir() {
+                self.create_dirs(&path)?;
+            } else if entry.is_symlink() {
+                self.skip(&name, "링크");
+            } else {
+                let modified = entry
+                    .last_modified()
+                    .and_then(|t| chrono::NaiveDateTime::try_from(t).ok())
+                    .and_then(|t| t.and_local_timezone(chrono::Local).earliest())
+                    .map(|t| FileTime::from_unix_time(t.timestamp(), 0));
+                let mode = entry.unix_mode();
+                self.write_file(&path, &name, &mut entry, modified, mode)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn tar<R: Read>(&mut self, reader: R) -> Result<(), String> {
+        let mut archive = tar::Archive::new(reader);
+        let entries = archive
+            .entries()
+            .map_err(|e| format!("압축 파일을 읽을 수 없습니다: {}", e))?;
+
+        for entry in entries {
+            let mut entry = entry.map_err(|e| format!("압축 파일을 읽을 수 없습니다: {}", e))?;
+            self.count_entry()?;
+
+            let name = entry
+                .path()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let Some(path) = entry.path().ok().and_then(|p| self.entry_path(&p)) else {
+                self.skip(&name, "안전하지 않은 경로");
+                continue;
+            };
+
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_dir() {
+                self.create_dirs(&path)?;
+            } else if entry_type.is_file() || entry_type.is_contiguous() {
+                let modified = entry
+                    .header()
+                    .mtime()
+                    .ok()
+                    .map(|t| FileTime::from_unix_time(t as i64, 0));
+                let mode = entry.header().mode().ok();
+                self.write_file(&path, &name, &mut entry, modified, mode)?;
+            } else if entry_type.is_symlink() || entry_type.is_hard_link() {
+                self.skip(&name, "링크");
+            } else if !entry_type.is_pax_global_extensions() {
+                self.skip(&name, "특수 파일");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The path an entry extracts to, or None when it would leave the destination
+    fn entry_path(&self, name: &Path) -> Option<PathBuf> {
+        let mut path = self.dest.to_path_buf();
+        let mut depth = 0;
+        for component in name.components() {
+            match component {
+                Component::Normal(part) => {
+                    path.push(part);
+                    depth += 1;
+                }
+                Component::CurDir => {}
+                _ => return None,
+            }
+        }
+        (depth > 0).then_some(path)
+    }
+
+    fn count_entry(&mut self) -> Result<(), String> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(format!(
+                "압축 파일 항목이 너무 많습니다 ({}개 초과)",
+                self.limits.max_entries
+            ));
+        }
+        Ok(())
+    }
+
+    fn skip(&mut self, name: &str, reason: &str) {
+        self.extracted.skipped.push(format!("{}: {}", name, reason));
+    }
+
+    /// Create a folder and any missing parents inside the destination, recording each
+    fn create_dirs(&mut self, dir: &Path) -> Result<(), String> {
+        let missing: Vec<PathBuf> = dir
+            .ancestors()
+            .take_while(|d| *d != self.dest)
+            .filter(|d| fs::symlink_metadata(d).is_err())
+            .map(Path::to_path_buf)
+            .collect();
+
+        for dir in missing.into_iter().rev() {
+            fs::create_dir(&dir).map_err(|e| format!("폴더를 만들 수 없습니다 {}: {}", dir.display(), e))?;
+            self.extracted.created_dirs.push(dir);
+        }
+        Ok(())
+    }
+
+    /// Write one file, counting the bytes actually produced against the limits
+    fn write_file(
+        &mut self,
+        path: &Path,
+        name: &str,
+        reader: &mut dyn Read,
+        modified: Option<FileTime>,
+        mode: Option<u32>,
+    ) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            self.create_dirs(parent)?;
+        }
+
+        let file = match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                self.skip(name, "같은 이름의 항목이 이미 있음");
+                return Ok(());
+            }
+            Err(e) => return Err(format!("{}: {}", name, e)),
+        };
+        self.extracted.files.push(path.to_path_buf());
+
+        let mut writer = BufWriter::new(file);
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| format!("압축 해제 실패 {}: {}", name, e))?;
+            if read == 0 {
+                break;
+            }
+            self.charge(read as u64)?;
+            writer
+                .write_all(&buffer[..read])
+                .map_err(|e| format!("{}: {}", name, e))?;
+        }
+        writer.flush().map_err(|e| format!("{}: {}", name, e))?;
+        drop(writer);
+
+        if let Some(mode) = mode {
+            set_executable(path, mode);
+        }
+        if let Some(modified) = modified {
+            let _ = filetime::set_file_mtime(path, modified);
+        }
+        Ok(())
+    }
+
+    fn charge(&mut self, bytes: u64) -> Result<(), String> {
+        self.extracted.total_size += bytes;
+        let total = self.extracted.total_size;
+
+        let too_large = total > self.limits.max_size;
+        let too_compressed = total > RATIO_CHECK_FLOOR
+            && total > self.archive_size.saturating_mul(self.limits.max_ratio);
+        if too_large || too_compressed {
+            return Err("압축 해제 크기가 제한을 넘었습니다 (압축 폭탄일 수 있습니다)".to_string());
+        }
+        Ok(())
+    }
+
+    /// Remove everything written, for a failed extraction
+    fn rollback(&self) {
+        for file in &self.extracted.files {
+            let _ = fs::remove_file(file);
+        }
+        for dir in self.extracted.created_dirs.iter().rev() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+/// Keep the executable bits an entry was stored with
+#[cfg(unix)]
+fn set_executable(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+
+    if mode & 0o111 == 0 {
+        return;
+    }
+    if let Ok(metadata) = fs::metadata(path) {
+        let mut permissions = metadata.permissions();
+        permissions.set_mode(permissions.mode() | (mode & 0o111));
+        let _ = fs::set_permissions(path, permissions);
+    }
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path, _mode: u32) {}
+
+/// What an archive was created from
+#[derive(Debug, Default)]
+pub struct Packed {
+    pub files_added: usize,
+    pub total_size: u64,
+    /// Items left out (links, special files, name clashes), one message each
+    pub skipped: Vec<String>,
+}
+
+/// A file or folder to store, with its name inside the archive
+struct PackEntry {
+    source: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+/// Create an archive at `archive` (which must not exist) from files and folders.
+/// Each item is stored under its own name; folders are stored with their contents.
+pub fn create(sources: &[PathBuf], archive: &Path) -> Result<Packed, String> {
+    let format = ArchiveFormat::detect(archive).ok_or("지원하지 않는 압축 형식입니다")?;
+    let mut packed = Packed::default();
+    let entries = collect_entries(sources, archive, &mut packed);
+
+    if entries.is_empty() {
+        return Err("압축할 파일이 없습니다".to_string());
+    }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(archive)
+        .map_err(|e| format!("압축 파일을 만들 수 없습니다: {}", e))?;
+    let writer = BufWriter::new(file);
+
+    let result = match format {
+        ArchiveFormat::Zip => write_zip(writer, &entries, &mut packed).map(|_| ()),
+        ArchiveFormat::Tar => write_tar(writer, &entries, &mut packed).map(|_| ()),
+        ArchiveFormat::TarGz => {
+            let encoder = GzEncoder::new(writer, Compression::default());
+            write_tar(encoder, &entries, &mut packed)
+                .and_then(|encoder| encoder.finish().map_err(|e| e.to_string()))
+                .map(|_| ())
+        }
+        ArchiveFormat::TarZst => zstd::Encoder::new(writer, 0)
+            .map_err(|e| e.to_string())
+            .and_then(|encoder| write_tar(encoder, &entries, &mut packed))
+            .and_then(|encoder| encoder.finish().map_err(|e| e.to_string()))
+            .map(|_| ()),
+    };
+
+    match result {
+        Ok(()) => Ok(packed),
+        Err(e) => {
+            let _ = fs::remove_file(archive);
+            Err(format!("압축 실패: {}", e))
+        }
+    }
+}
+
+fn collect_entries(sources: &[PathBuf], archive: &Path, packed: &mut Packed) -> Vec<PackEntry> {
+    let mut entries: Vec<PackEntry> = Vec::new();
+    let mut top_names: Vec<String> = Vec::new();
+
+    for source in sources {
+        let Some(top) = source.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if top_names.contains(&top) {
+            packed.skipped.push(format!("{}: 같은 이름의 항목이 이미 있음", source.display()));
+            continue;
+        }
+        top_names.push(top.clone());
+
+        for entry in WalkDir::new(source).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            // The archive may be created inside a folder being archived
+            if path == archive {
+                continue;
+            }
+
+            let relative = path.strip_prefix(source).unwrap_or(path);
+            let mut name = top.clone();
+            for part in relative.components() {
+                name.push('/');
+                name.push_str(&part.as_os_str().to_string_lossy());
+            }
+
+            let file_type = entry.file_type();
+            if file_type.is_dir() {
+                entries.push(PackEntry { source: path.to_path_buf(), name, is_dir: true });
+            } else if file_type.is_file() {
+                entries.push(PackEntry { source: path.to_path_buf(), name, is_dir: false });
+            } else {
+                packed.skipped.push(format!("{}: 링크나 특수 파일", path.display()));
+            }
+        }
+    }
+
+    entries
+}
+
+fn write_zip<W: Write + io::Seek>(
+    writer: W,
+    entries: &[PackEntry],
+    packed: &mut Packed,
+) -> Result<W, String> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for entry in entries {
+        let metadata = fs::metadata(&entry.source).map_err(|e| e.to_string())?;
+        let mut entry_options = options;
+        if let Some(modified) = metadata
+            .modified()
+            .ok()
+            .map(|t| chrono::DateTime::<chrono::Local>::from(t).naive_local())
+            .and_then(|t| zip::DateTime::try_from(t).ok())
+        {
+            entry_options = entry_options.last_modified_time(modified);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            entry_options = entry_options.unix_permissions(metadata.permissions().mode());
+        }
+
+        if entry.is_dir {
+            zip.add_directory(entry.name.as_str(), entry_options)
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        zip.start_file(
+            entry.name.as_str(),
+            entry_options.large_file(metadata.len() >= u32::MAX as u64),
+        )
+        .map_err(|e| e.to_string())?;
+        let mut file = File::open(&entry.source).map_err(|e| format!("{}: {}", entry.name, e))?;
+        let copied = io::copy(&mut file, &mut zip).map_err(|e| format!("{}: {}", entry.name, e))?;
+        packed.files_added += 1;
+        packed.total_size += copied;
+    }
+
+    zip.finish().map_err(|e| e.to_string())
+}
+
+fn write_tar<W: Write>(writer: W, entries: &[PackEntry], packed: &mut Packed) -> Result<W, String> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+
+    for entry in entries {
+        if entry.is_dir {
+            builder
+                .append_dir(&entry.name, &entry.source)
+                .map_err(|e| format!("{}: {}", entry.name, e))?;
+            continue;
+        }
+
+        let mut file = File::open(&entry.source).map_err(|e| format!("{}: {}", entry.name, e))?;
+        builder
+            .append_file(&entry.name, &mut file)
+            .map_err(|e| format!("{}: {}", entry.name, e))?;
+        packed.files_added += 1;
+        packed.total_size += file.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+
+    builder.into_inner().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_folder(root: &Path) -> PathBuf {
+        let folder = root.join("report");
+        fs::create_dir_all(folder.join("images")).unwrap();
+        fs::write(folder.join("summary.txt"), b"quarterly numbers").unwrap();
+        fs::write(folder.join("images/chart.png"), vec![7u8; 5000]).unwrap();
+        folder
+    }
+
+    #[test]
+    fn test_detect_and_name() {
+        assert_eq!(ArchiveFormat::detect(Path::new("a.tar.gz")), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::detect(Path::new("a.TZST")), Some(ArchiveFormat::TarZst));
+        assert_eq!(ArchiveFormat::detect(Path::new("a.zip")), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::detect(Path::new("a.rar")), None);
+        assert_eq!(ArchiveFormat::detect(Path::new(".zip")), None);
+
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("photos.tar.gz");
+        assert_eq!(extract_dir_for(&archive), Some(dir.path().join("photos")));
+        fs::write(&archive, b"").unwrap();
+        assert_eq!(unique_archive_path(&archive), dir.path().join("photos_1.tar.gz"));
+    }
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let dir = tempdir().unwrap();
+        let folder = sample_folder(dir.path());
+
+        for name in ["out.zip", "out.tar", "out.tar.gz", "out.tar.zst"] {
+            let archive = dir.path().join(name);
+            let packed = create(std::slice::from_ref(&folder), &archive).unwrap();
+            assert_eq!(packed.files_added, 2, "{}", name);
+
+            let dest = dir.path().join(format!("{}-extracted", name));
+            let extracted = extract(&archive, &dest, &ExtractLimits::default()).unwrap();
+            assert_eq!(extracted.files.len(), 2, "{}", name);
+            assert_eq!(extracted.created_dirs[0], dest);
+            assert_eq!(
+                fs::read(dest.join("report/summary.txt")).unwrap(),
+                b"quarterly numbers"
+            );
+            assert_eq!(fs::read(dest.join("report/images/chart.png")).unwrap().len(), 5000);
+        }
+    }
+
+    #[test]
+    fn test_zip_slip_entries_are_skipped() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("evil.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file("../escaped.txt", options).unwrap();
+        zip.write_all(b"x").unwrap();
+        zip.start_file("ok.txt", options).unwrap();
+        zip.write_all(b"y").unwrap();
+        zip.finish().unwrap();
+
+        let dest = dir.path().join("evil");
+        let extracted = extract(&archive, &dest, &ExtractLimits::default()).unwrap();
+        assert_eq!(extracted.files, vec![dest.join("ok.txt")]);
+        assert_eq!(extracted.skipped.len(), 1);
+        assert!(!dir.path().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn test_size_limit_rolls_back() {
+        let dir = tempdir().unwrap();
+        let folder = sample_folder(dir.path());
+        let archive = dir.path().join("big.tar.gz");
+        create(&[folder], &archive).unwrap();
+
+        let limits = ExtractLimits {
+            max_size: 1000,
+            ..ExtractLimits::default()
+        };
+        let dest = dir.path().join("big");
+        assert!(extract(&archive, &dest, &limits).is_err());
+        assert!(!dest.exists());
+    }
+}
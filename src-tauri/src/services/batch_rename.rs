@@ -0,0 +1,307 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One rename in a batch
+#[derive(Debug, Clone)]
+pub struct RenameOp {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct BatchRenameOutcome {
+    /// Completed renames, in the order of the requested operations
+    pub renamed: Vec<(PathBuf, PathBuf)>,
+    /// Operations that were not performed, with the reason
+    pub errors: Vec<(PathBuf, String)>,
+    /// Rename cycles (e.g. a ↔ b) resolved through temporary names
+    pub cycles: usize,
+}
+
+/// Execute renames as one batch.
+///
+/// Renames whose target is the current name of another file in the batch (swaps,
+/// chains, cycles) and case-only renames go through two phases: every such file is
+/// first moved to a temporary name, then to its target. Independent renames are done
+/// directly. A target already taken by a file outside the batch is never overwritten.
+pub fn execute_batch(ops: &[RenameOp]) -> BatchRenameOutcome {
+    let mut outcome = BatchRenameOutcome::default();
+
+    // Index of the batch source a target currently points at (None when free)
+    let mut occupant: Vec<Option<usize>> = vec![None; ops.len()];
+    let mut valid = vec![true; ops.len()];
+
+    for (i, op) in ops.iter().enumerate() {
+        if op.from == op.to {
+            valid[i] = false;
+            continue;
+        }
+        if fs::symlink_metadata(&op.from).is_err() {
+            outcome.errors.push((op.from.clone(), "원본 파일을 찾을 수 없습니다".to_string()));
+            valid[i] = false;
+            continue;
+        }
+        if ops[..i].iter().zip(&valid).any(|(other, ok)| *ok && other.to == op.to) {
+            outcome.errors.push((op.from.clone(), "대상 이름이 중복됩니다".to_string()));
+            valid[i] = false;
+            continue;
+        }
+
+        if fs::symlink_metadata(&op.to).is_ok() {
+            // On case-insensitive volumes the target of a case-only rename is the file itself
+            match ops.iter().position(|other| is_same_file(&op.to, &other.from)) {
+                Some(j) => occupant[i] = Some(j),
+                None => {
+                    outcome.errors.push((op.from.clone(), "이미 존재하는 파일입니다".to_string()));
+                    valid[i] = false;
+                }
+            }
+        }
+    }
+
+    // A target held by a source that is not moving stays taken
+    loop {
+        let blocked: Vec<usize> = (0..ops.len())
+            .filter(|&i| valid[i] && matches!(occupant[i], Some(j) if j != i && !valid[j]))
+            .collect();
+        if blocked.is_empty() {
+            break;
+        }
+        for i in blocked {
+            outcome.errors.push((ops[i].from.clone(), "이미 존재하는 파일입니다".to_string()));
+            valid[i] = false;
+        }
+    }
+
+    outcome.cycles = count_cycles(&occupant, &valid);
+
+    let depended_on: Vec<bool> = (0..ops.len())
+        .map(|j| (0..ops.len()).any(|i| valid[i] && occupant[i] == Some(j)))
+        .collect();
+    let two_phase: Vec<bool> = (0..ops.len())
+        .map(|i| valid[i] && (occupant[i].is_some() || depended_on[i]))
+        .collect();
+
+    let mut done: Vec<Option<PathBuf>> = vec![None; ops.len()];
+
+    // Independent renames
+    for (i, op) in ops.iter().enumerate() {
+        if !valid[i] || two_phase[i] {
+            continue;
+        }
+        match rename_no_clobber(&op.from, &op.to) {
+            Ok(()) => done[i] = Some(op.to.clone()),
+            Err(e) => outcome.errors.push((op.from.clone(), e)),
+        }
+    }
+
+    // Phase 1: move dependent files out of the way
+    let mut temps: Vec<Option<PathBuf>> = vec![None; ops.len()];
+    for (i, op) in ops.iter().enumerate() {
+        if !two_phase[i] {
+            continue;
+        }
+        let temp = temp_name(&op.from, i);
+        match fs::rename(&op.from, &temp) {
+            Ok(()) => temps[i] = Some(temp),
+            Err(e) => outcome.errors.push((op.from.clone(), e.to_string())),
+        }
+    }
+
+    // Phase 2: temporary names to targets, restoring the original name on failure
+    for (i, op) in ops.iter().enumerate() {
+        let Some(temp) = temps[i].take() else {
+            continue;
+        };
+        match rename_no_clobber(&temp, &op.to) {
+            Ok(()) => done[i] = Some(op.to.clone()),
+            Err(e) => {
+                let message = match rename_no_clobber(&temp, &op.from) {
+                    Ok(()) => e,
+                    Err(_) => format!("{} (임시 이름으로 남음: {})", e, temp.display()),
+                };
+                outcome.errors.push((op.from.clone(), message));
+            }
+        }
+    }
+
+    for (i, op) in ops.iter().enumerate() {
+        if let Some(to) = done[i].take() {
+            outcome.renamed.push((op.from.clone(), to));
+        }
+    }
+
+    outcome
+}
+
+/// Rename unless something else already exists at `to`
+fn rename_no_clobber(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::symlink_metadata(to).is_ok() && !is_same_file(from, to) {
+        return Err("이미 존재하는 파일입니다".to_string());
+    }
+    fs::rename(from, to).map_err(|e| e.to_string())
+}
+
+/// Count dependency cycles of two or more files. Every target is held by at most
+/// one source, so following `occupant` from any file walks a single path.
+fn count_cycles(occupant: &[Option<usize>], valid: &[bool]) -> usize {
+    // 0 = unvisited, 1 = on the current path, 2 = finished
+    let mut state = vec![0u8; occupant.len()];
+    let mut cycles = 0;
+
+    for start in 0..occupant.len() {
+        let mut path = Vec::new();
+        let mut node = Some(start);
+
+        while let Some(i) = node {
+            if !valid[i] || state[i] == 2 {
+                break;
+            }
+            if state[i] == 1 {
+                if occupant[i] != Some(i) {
+                    cycles += 1;
+                }
+                break;
+            }
+            state[i] = 1;
+            path.push(i);
+            node = occupant[i];
+        }
+
+        for i in path {
+            state[i] = 2;
+        }
+    }
+
+    cycles
+}
+
+/// Whether two paths name the same file (e.g. "photo.JPG" and "photo.jpg" on a
+/// case-insensitive volume)
+#[cfg(unix)]
+pub fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Size and modification time (ms since the epoch) of a file, recorded with a rename
+/// so an undo can tell whether the file was changed afterwards. Renaming keeps both.
+pub fn file_stamp(path: &Path) -> Option<(u64, i64)> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    Some((metadata.len(), modified))
+}
+
+/// Unused hidden name next to `path` for the first phase
+fn temp_name(path: &Path, index: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    (0..)
+        .map(|attempt| path.with_file_name(format!(".{}.renaming-{}-{}", name, index, attempt)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| path.with_file_name(format!(".{}.renaming", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn op(from: &Path, to: &Path) -> RenameOp {
+        RenameOp {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_swap_and_cycle() {
+        let dir = tempdir().unwrap();
+        let (a, b, c) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"));
+        fs::write(&a, b"A").unwrap();
+        fs::write(&b, b"B").unwrap();
+        fs::write(&c, b"C").unwrap();
+
+        let outcome = execute_batch(&[op(&a, &b), op(&b, &a)]);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(outcome.cycles, 1);
+        assert_eq!(fs::read(&a).unwrap(), b"B");
+        assert_eq!(fs::read(&b).unwrap(), b"A");
+
+        // a -> b -> c -> a
+        let outcome = execute_batch(&[op(&a, &b), op(&b, &c), op(&c, &a)]);
+        assert_eq!(outcome.renamed.len(), 3);
+        assert_eq!(outcome.cycles, 1);
+        assert_eq!(fs::read(&a).unwrap(), b"C");
+        assert_eq!(fs::read(&b).unwrap(), b"B");
+        assert_eq!(fs::read(&c).unwrap(), b"A");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_chain_and_case_only() {
+        let dir = tempdir().unwrap();
+        let (upper, lower) = (dir.path().join("photo.JPG"), dir.path().join("photo.jpg"));
+        let (a, b, c) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"));
+        fs::write(&upper, b"img").unwrap();
+        fs::write(&a, b"A").unwrap();
+        fs::write(&b, b"B").unwrap();
+
+        // b moves to the free name c before a takes its place
+        let outcome = execute_batch(&[op(&upper, &lower), op(&a, &b), op(&b, &c)]);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(outcome.cycles, 0);
+        assert_eq!(fs::read(&lower).unwrap(), b"img");
+        assert_eq!(fs::read(&b).unwrap(), b"A");
+        assert_eq!(fs::read(&c).unwrap(), b"B");
+    }
+
+    #[test]
+    fn test_stamp_survives_rename() {
+        let dir = tempdir().unwrap();
+        let (a, b) = (dir.path().join("a"), dir.path().join("b"));
+        fs::write(&a, b"data").unwrap();
+
+        let before = file_stamp(&a).unwrap();
+        execute_batch(&[op(&a, &b)]);
+        assert_eq!(file_stamp(&b), Some(before));
+        assert_eq!(file_stamp(&a), None);
+    }
+
+    #[test]
+    fn test_existing_target_is_not_overwritten() {
+        let dir = tempdir().unwrap();
+        let (a, b, c) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"));
+        fs::write(&a, b"A").unwrap();
+        fs::write(&b, b"B").unwrap();
+        fs::write(&c, b"C").unwrap();
+
+        // b is taken by a file outside the batch, so c -> a cannot happen either
+        let outcome = execute_batch(&[op(&a, &b), op(&c, &a)]);
+        assert!(outcome.renamed.is_empty());
+        assert_eq!(outcome.errors.len(), 2);
+        assert_eq!(fs::read(&a).unwrap(), b"A");
+        assert_eq!(fs::read(&b).unwrap(), b"B");
+        assert_eq!(fs::read(&c).unwrap(), b"C");
+    }
+}
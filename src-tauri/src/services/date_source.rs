@@ -0,0 +1,255 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Where the date used for date subfolders (and date renames) comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DateSource {
+    Exif,
+    Filename,
+    Created,
+    #[default]
+    Modified,
+}
+
+impl DateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateSource::Exif => "exif",
+            DateSource::Filename => "filename",
+            DateSource::Created => "created",
+            DateSource::Modified => "modified",
+        }
+    }
+
+    pub fn korean_label(&self) -> &'static str {
+        match self {
+            DateSource::Exif => "촬영일(EXIF)",
+            DateSource::Filename => "파일명 날짜",
+            DateSource::Created => "생성일",
+            DateSource::Modified => "수정일",
+        }
+    }
+
+    /// Parse a stored value, falling back to modified time for unknown values
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "exif" => DateSource::Exif,
+            "filename" => DateSource::Filename,
+            "created" => DateSource::Created,
+            _ => DateSource::Modified,
+        }
+    }
+
+    /// Sources tried in order when the preferred one has no date
+    pub fn fallback_chain(&self) -> &'static [DateSource] {
+        match self {
+            DateSource::Exif => &[
+                DateSource::Exif,
+                DateSource::Filename,
+                DateSource::Created,
+                DateSource::Modified,
+            ],
+            DateSource::Filename => &[
+                DateSource::Filename,
+                DateSource::Exif,
+                DateSource::Created,
+                DateSource::Modified,
+            ],
+            DateSource::Created => &[DateSource::Created, DateSource::Modified],
+            DateSource::Modified => &[DateSource::Modified],
+        }
+    }
+}
+
+/// A date resolved for a file, together with the source that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedDate {
+    pub datetime: NaiveDateTime,
+    pub source: DateSource,
+}
+
+/// Resolve a file's date by walking the fallback chain of the preferred source
+pub fn resolve_file_date(
+    path: &Path,
+    metadata: Option<&fs::Metadata>,
+    preferred: DateSource,
+) -> Option<ResolvedDate> {
+    preferred.fallback_chain().iter().find_map(|source| {
+        let datetime = match source {
+            DateSource::Exif => read_exif_date(path),
+            DateSource::Filename => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(parse_filename_date),
+            DateSource::Created => metadata.and_then(|m| m.created().ok()).map(to_local_naive),
+            DateSource::Modified => metadata.and_then(|m| m.modified().ok()).map(to_local_naive),
+        }?;
+
+        Some(ResolvedDate {
+            datetime,
+            source: *source,
+        })
+    })
+}
+
+/// Format a date as a (possibly nested) subfolder name
+pub fn format_date_folder(datetime: &NaiveDateTime, format: &str) -> String {
+    match format {
+        "YYYY-MM" => datetime.format("%Y-%m").to_string(),
+        "YYYY/MM" => datetime.format("%Y/%m").to_string(),
+        "YYYY" => datetime.format("%Y").to_string(),
+        _ => datetime.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// Resolve a file's date and format it as a subfolder name.
+/// Returns "Unknown" with no source when no date could be determined.
+pub fn resolve_date_folder(
+    path: &Path,
+    metadata: Option<&fs::Metadata>,
+    preferred: DateSource,
+    format: &str,
+) -> (String, Option<DateSource>) {
+    match resolve_file_date(path, metadata, preferred) {
+        Some(resolved) => (
+            format_date_folder(&resolved.datetime, format),
+            Some(resolved.source),
+        ),
+        None => ("Unknown".to_string(), None),
+    }
+}
+
+fn to_local_naive(time: std::time::SystemTime) -> NaiveDateTime {
+    let datetime: chrono::DateTime<chrono::Local> = time.into();
+    datetime.naive_local()
+}
+
+/// Read EXIF DateTimeOriginal (falling back to DateTime) from an image
+pub fn read_exif_date(path: &Path) -> Option<NaiveDateTime> {
+    let file = fs::File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let exif = exif::Reader::new().read_from_container(&mut reader).ok()?;
+
+    [exif::Tag::DateTimeOriginal, exif::Tag::DateTime]
+        .iter()
+        .find_map(|tag| {
+            let field = exif.get_field(*tag, exif::In::PRIMARY)?;
+            match &field.value {
+                exif::Value::Ascii(values) => {
+                    let dt = exif::DateTime::from_ascii(values.first()?).ok()?;
+                    NaiveDate::from_ymd_opt(dt.year as i32, dt.month as u32, dt.day as u32)?
+                        .and_hms_opt(dt.hour as u32, dt.minute as u32, dt.second as u32)
+                }
+                _ => None,
+            }
+        })
+}
+
+fn filename_date_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // Matches IMG_20250101_123456, KakaoTalk_20260105_..., Screenshot 2026-01-05 at 10.20.30
+        Regex::new(
+            r"(?:^|\D)((?:19|20)\d{2})[-_.]?(\d{2})[-_.]?(\d{2})(?:(?:[ _T-]|\s+at\s+)(\d{2})[.:\-_]?(\d{2})[.:\-_]?(\d{2}))?",
+        )
+        .unwrap()
+    })
+}
+
+/// Parse a date embedded in a filename
+pub fn parse_filename_date(file_name: &str) -> Option<NaiveDateTime> {
+    filename_date_regex().captures_iter(file_name).find_map(|caps| {
+        let year: i32 = caps[1].parse().ok()?;
+        let month: u32 = caps[2].parse().ok()?;
+        let day: u32 = caps[3].parse().ok()?;
+        let date = NaiveDate::from_ymd_opt(year, month, day)?;
+
+        let time = match (caps.get(4), caps.get(5), caps.get(6)) {
+            (Some(h), Some(m), Some(s)) => date.and_hms_opt(
+                h.as_str().parse().ok()?,
+                m.as_str().parse().ok()?,
+                s.as_str().parse().ok()?,
+            ),
+            _ => None,
+        };
+
+        time.or_else(|| date.and_hms_opt(0, 0, 0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_filename_date_patterns() {
+        let dt = parse_filename_date("IMG_20250101_123456.jpg").unwrap();
+        assert_eq!(dt, ymd(2025, 1, 1).and_hms_opt(12, 34, 56).unwrap());
+
+        let dt = parse_filename_date("KakaoTalk_20260105_101112345.jpg").unwrap();
+        assert_eq!(dt.date(), ymd(2026, 1, 5));
+
+        let dt = parse_filename_date("Screenshot 2026-01-05 at 10.20.30.png").unwrap();
+        assert_eq!(dt, ymd(2026, 1, 5).and_hms_opt(10, 20, 30).unwrap());
+
+        let dt = parse_filename_date("report_2024.03.15.pdf").unwrap();
+        assert_eq!(dt.date(), ymd(2024, 3, 15));
+    }
+
+    #[test]
+    fn test_parse_filename_date_rejects_invalid() {
+        assert!(parse_filename_date("notes.txt").is_none());
+        assert!(parse_filename_date("IMG_20251340_000000.jpg").is_none());
+        assert!(parse_filename_date("invoice_123456789.pdf").is_none());
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_modified() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("plain.jpg");
+        fs::write(&path, b"not really a jpeg").unwrap();
+        let metadata = fs::metadata(&path).ok();
+
+        let resolved = resolve_file_date(&path, metadata.as_ref(), DateSource::Exif).unwrap();
+        assert!(matches!(
+            resolved.source,
+            DateSource::Created | DateSource::Modified
+        ));
+    }
+
+    #[test]
+    fn test_resolve_prefers_filename() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("IMG_20200202_080910.jpg");
+        fs::write(&path, b"").unwrap();
+        let metadata = fs::metadata(&path).ok();
+
+        let resolved = resolve_file_date(&path, metadata.as_ref(), DateSource::Filename).unwrap();
+        assert_eq!(resolved.source, DateSource::Filename);
+        assert_eq!(format_date_folder(&resolved.datetime, "YYYY-MM"), "2020-02");
+    }
+
+    #[test]
+    fn test_date_source_parse_roundtrip() {
+        for source in [
+            DateSource::Exif,
+            DateSource::Filename,
+            DateSource::Created,
+            DateSource::Modified,
+        ] {
+            assert_eq!(DateSource::parse(source.as_str()), source);
+        }
+        assert_eq!(DateSource::parse("unknown"), DateSource::Modified);
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+/// Alternative spellings and the extension they are unified to
+const ALIASES: &[(&str, &str)] = &[
+    ("jpeg", "jpg"),
+    ("jpe", "jpg"),
+    ("jfif", "jpg"),
+    ("htm", "html"),
+    ("tif", "tiff"),
+    ("yml", "yaml"),
+    ("markdown", "md"),
+    ("mpeg", "mpg"),
+    ("aif", "aiff"),
+];
+
+/// Formats stored in a container that sniffs as another type (ZIP, OLE, MP4 ...).
+/// A file with one of these extensions is not mislabeled when the container matches.
+const CONTAINER_FORMATS: &[(&str, &[&str])] = &[
+    (
+        "zip",
+        &[
+            "docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "jar", "apk", "ipa", "xpi",
+            "hwpx", "pages", "numbers", "key", "appx",
+        ],
+    ),
+    ("doc", &["xls", "ppt", "msi", "hwp"]),
+    ("mp4", &["m4a", "m4v", "mov", "3gp"]),
+    ("xml", &["svg"]),
+];
+
+/// Canonical spelling of an extension (without the dot, lowercase)
+pub fn unify_alias(extension: &str) -> String {
+    let lower = extension.to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(lower)
+}
+
+/// Extension matching the file's content, when the content has a recognizable signature
+pub fn sniff_extension(path: &Path) -> Option<&'static str> {
+    infer::get_from_path(path).ok().flatten().map(|kind| kind.extension())
+}
+
+/// Whether `extension` already describes content sniffed as `sniffed`
+pub fn matches_content(extension: &str, sniffed: &str) -> bool {
+    let extension = unify_alias(extension);
+    if extension == unify_alias(sniffed) {
+        return true;
+    }
+
+    CONTAINER_FORMATS
+        .iter()
+        .any(|(container, formats)| *container == sniffed && formats.contains(&extension.as_str()))
+}
+
+/// Extension a file should have based on its content: None when the content is not
+/// recognized or the current extension already fits it
+pub fn corrected_extension(path: &Path, extension: &str) -> Option<&'static str> {
+    let sniffed = sniff_extension(path)?;
+    if !extension.is_empty() && matches_content(extension, sniffed) {
+        return None;
+    }
+    Some(sniffed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const PNG_HEADER: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D];
+
+    #[test]
+    fn test_unify_alias() {
+        assert_eq!(unify_alias("JPEG"), "jpg");
+        assert_eq!(unify_alias("htm"), "html");
+        assert_eq!(unify_alias("Png"), "png");
+    }
+
+    #[test]
+    fn test_corrected_extension() {
+        let dir = tempdir().unwrap();
+        let mislabeled = dir.path().join("photo.pdf");
+        let missing = dir.path().join("photo");
+        let text = dir.path().join("notes");
+        fs::write(&mislabeled, PNG_HEADER).unwrap();
+        fs::write(&missing, PNG_HEADER).unwrap();
+        fs::write(&text, b"plain text").unwrap();
+
+        assert_eq!(corrected_extension(&mislabeled, "pdf"), Some("png"));
+        assert_eq!(corrected_extension(&missing, ""), Some("png"));
+        assert_eq!(corrected_extension(&mislabeled, "PNG"), None);
+        assert_eq!(corrected_extension(&text, ""), None);
+    }
+
+    #[test]
+    fn test_container_formats_are_not_mislabeled() {
+        assert!(matches_content("docx", "zip"));
+        assert!(matches_content("jpeg", "jpg"));
+        assert!(!matches_content("pdf", "zip"));
+    }
+}
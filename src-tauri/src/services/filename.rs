@@ -0,0 +1,125 @@
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// Characters Windows, FAT and exFAT do not allow in a file name
+const ILLEGAL_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension ("CON.txt" is also reserved)
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Longest name (in UTF-8 bytes) most file systems accept
+pub const MAX_NAME_BYTES: usize = 255;
+
+/// Composed (NFC) form. macOS hands out decomposed (NFD) Hangul, which looks the same
+/// but compares differently, so names are compared in this form.
+pub fn to_nfc(value: &str) -> String {
+    if is_nfc(value) {
+        value.to_string()
+    } else {
+        value.nfc().collect()
+    }
+}
+
+/// Decomposed (NFD) form, as stored by older macOS file systems
+pub fn to_nfd(value: &str) -> String {
+    value.nfd().collect()
+}
+
+/// Make a file name valid on Windows, FAT and exFAT: illegal and control characters
+/// become '_', trailing dots and spaces are removed, reserved device names get a '_'
+/// suffix and the name is shortened to `MAX_NAME_BYTES` keeping the extension.
+pub fn sanitize_filename(name: &str) -> String {
+    let replaced: String = to_nfc(name)
+        .chars()
+        .map(|c| {
+            if ILLEGAL_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let mut sanitized = replaced.trim_end_matches(['.', ' ']).trim_start().to_string();
+    if sanitized.is_empty() {
+        return "_".to_string();
+    }
+
+    // Windows looks at the part before the first dot ("CON.tar.gz" is reserved too)
+    let base_end = sanitized.find('.').unwrap_or(sanitized.len());
+    if RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(&sanitized[..base_end])) {
+        sanitized.insert(base_end, '_');
+    }
+
+    truncate_name(&sanitized, MAX_NAME_BYTES)
+}
+
+/// Shorten a name to at most `max_bytes`, cutting the stem on a character boundary
+pub fn truncate_name(name: &str, max_bytes: usize) -> String {
+    if name.len() <= max_bytes {
+        return name.to_string();
+    }
+
+    let (stem, extension) = split_extension(name);
+    // An absurdly long extension is cut like any other text
+    if extension.len() >= max_bytes {
+        return truncate_bytes(name, max_bytes).to_string();
+    }
+
+    let stem = truncate_bytes(stem, max_bytes - extension.len()).trim_end_matches(['.', ' ']);
+    format!("{}{}", stem, extension)
+}
+
+fn truncate_bytes(value: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(value.len());
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+/// Split "name.ext" into ("name", ".ext"); dotfiles have no extension
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(index) if index > 0 => name.split_at(index),
+        _ => (name, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nfc_nfd_round_trip() {
+        let nfc = "한글 문서.txt";
+        let nfd = to_nfd(nfc);
+
+        assert_ne!(nfc, nfd);
+        assert!(nfd.chars().count() > nfc.chars().count());
+        assert_eq!(to_nfc(&nfd), nfc);
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("회의: 최종?.docx"), "회의_ 최종_.docx");
+        assert_eq!(sanitize_filename("report..."), "report");
+        assert_eq!(sanitize_filename("CON.txt"), "CON_.txt");
+        assert_eq!(sanitize_filename("CON.tar.gz"), "CON_.tar.gz");
+        assert_eq!(sanitize_filename("com1"), "com1_");
+        assert_eq!(sanitize_filename("console.txt"), "console.txt");
+        assert_eq!(sanitize_filename(" . "), "_");
+    }
+
+    #[test]
+    fn test_truncate_keeps_extension_and_char_boundaries() {
+        let long = format!("{}.pdf", "가".repeat(100));
+        let truncated = sanitize_filename(&long);
+
+        assert!(truncated.len() <= MAX_NAME_BYTES);
+        assert!(truncated.ends_with(".pdf"));
+        assert_eq!(truncated, format!("{}.pdf", "가".repeat(83)));
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use xxhash_rust::xxh3::Xxh3;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Hash the full contents of a file with 128-bit xxHash3.
+///
+/// Unlike the sampled hash used for duplicate scanning, this reads every byte,
+/// so two equal hashes can be treated as identical content.
+pub fn hash_file(path: &Path) -> Result<String, std::io::Error> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Xxh3::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:032x}", hasher.digest128()))
+}
+
+/// Check whether two files have identical contents (size first, then full hash)
+pub fn files_identical(a: &Path, b: &Path) -> Result<bool, std::io::Error> {
+    let size_a = fs::metadata(a)?.len();
+    let size_b = fs::metadata(b)?.len();
+
+    if size_a != size_b {
+        return Ok(false);
+    }
+
+    Ok(hash_file(a)? == hash_file(b)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hash_file_is_stable() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.txt");
+        fs::write(&path, b"hello world").unwrap();
+
+        let first = hash_file(&path).unwrap();
+        let second = hash_file(&path).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 32);
+    }
+
+    #[test]
+    fn test_files_identical() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.pdf");
+        let b = dir.path().join("b.pdf");
+        let c = dir.path().join("c.pdf");
+        fs::write(&a, b"same content").unwrap();
+        fs::write(&b, b"same content").unwrap();
+        fs::write(&c, b"other content").unwrap();
+
+        assert!(files_identical(&a, &b).unwrap());
+        assert!(!files_identical(&a, &c).unwrap());
+    }
+}
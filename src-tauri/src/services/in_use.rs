@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+
+use crate::services::settle::DeferReason;
+
+/// Files other processes currently have open, gathered once per batch.
+/// Only Linux exposes this (through /proc); elsewhere the set stays empty and
+/// detection relies on `check_file`.
+pub struct OpenFiles(HashSet<PathBuf>);
+
+impl OpenFiles {
+    #[cfg(target_os = "linux")]
+    pub fn scan() -> Self {
+        let own_pid = std::process::id().to_string();
+        let mut open = HashSet::new();
+
+        let Ok(processes) = fs::read_dir("/proc") else {
+            return OpenFiles(open);
+        };
+
+        for process in processes.flatten() {
+            let name = process.file_name();
+            let Some(pid) = name.to_str() else {
+                continue;
+            };
+            if pid == own_pid || !pid.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+
+            // Other users' processes are not readable and are skipped
+            let Ok(fds) = fs::read_dir(process.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                if let Ok(target) = fs::read_link(fd.path()) {
+                    if target.is_absolute() {
+                        open.insert(target);
+                    }
+                }
+            }
+        }
+
+        OpenFiles(open)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn scan() -> Self {
+        Self::empty()
+    }
+
+    pub fn empty() -> Self {
+        OpenFiles(HashSet::new())
+    }
+
+    /// Whether another process has the file open; /proc reports resolved paths
+    pub fn contains(&self, path: &Path) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+        self.0.contains(path)
+            || fs::canonicalize(path)
+                .map(|p| self.0.contains(&p))
+                .unwrap_or(false)
+    }
+}
+
+/// Check one file for other apps holding it: open elsewhere (per `open_files`),
+/// opened without sharing (Windows) or under an exclusive or shared lock
+pub fn check_file(path: &Path, open_files: &OpenFiles) -> Option<DeferReason> {
+    if open_files.contains(path) {
+        return Some(DeferReason::InUse);
+    }
+
+    let file = match open_for_check(path) {
+        Ok(file) => file,
+        Err(e) if is_sharing_violation(&e) => return Some(DeferReason::InUse),
+        // Unreadable files are left to the move itself to report
+        Err(_) => return None,
+    };
+
+    match file.try_lock_exclusive() {
+        Ok(()) => {
+            let _ = FileExt::unlock(&file);
+            None
+        }
+        Err(_) => Some(DeferReason::Locked),
+    }
+}
+
+#[cfg(windows)]
+fn open_for_check(path: &Path) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    // No sharing: fails while any other app has the file open
+    fs::OpenOptions::new().read(true).share_mode(0).open(path)
+}
+
+#[cfg(not(windows))]
+fn open_for_check(path: &Path) -> std::io::Result<File> {
+    File::open(path)
+}
+
+/// ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+#[cfg(windows)]
+fn is_sharing_violation(e: &std::io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(32) | Some(33))
+}
+
+#[cfg(not(windows))]
+fn is_sharing_violation(_e: &std::io::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_unused_file_passes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("budget.xlsx");
+        fs::write(&path, b"data").unwrap();
+
+        assert_eq!(check_file(&path, &OpenFiles::empty()), None);
+    }
+
+    #[test]
+    fn test_open_file_is_in_use() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("budget.xlsx");
+        fs::write(&path, b"data").unwrap();
+
+        let open = OpenFiles(HashSet::from([fs::canonicalize(&path).unwrap()]));
+        assert_eq!(check_file(&path, &open), Some(DeferReason::InUse));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_locked_file_is_deferred() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.db");
+        fs::write(&path, b"data").unwrap();
+
+        // flock locks belong to the open file, so a second handle sees the lock
+        let holder = File::open(&path).unwrap();
+        holder.lock_shared().unwrap();
+        assert_eq!(check_file(&path, &OpenFiles::empty()), Some(DeferReason::Locked));
+
+        FileExt::unlock(&holder).unwrap();
+        assert_eq!(check_file(&path, &OpenFiles::empty()), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::PathBuf;
+use std::sync::{Condvar, Mutex};
+
+/// Jobs that may run at once when the "max_concurrent_jobs" setting is missing
+pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlState {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+/// Pause and cancel switch for a running job. Long operations call `proceed`
+/// between files; direct command calls use a control that is never paused.
+pub struct JobControl {
+    state: Mutex<ControlState>,
+    changed: Condvar,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        JobControl {
+            state: Mutex::new(ControlState::Running),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Block while the job is paused. Returns false once it is cancelled and should stop.
+    pub fn proceed(&self) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return true;
+        };
+        while *state == ControlState::Paused {
+            state = match self.changed.wait(state) {
+                Ok(state) => state,
+                Err(_) => return true,
+            };
+        }
+        *state == ControlState::Running
+    }
+
+    pub fn pause(&self) {
+        self.set(ControlState::Paused, ControlState::Running);
+    }
+
+    pub fn resume(&self) {
+        self.set(ControlState::Running, ControlState::Paused);
+    }
+
+    pub fn cancel(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = ControlState::Cancelled;
+        }
+        self.changed.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state
+            .lock()
+            .map(|s| *s == ControlState::Cancelled)
+            .unwrap_or(false)
+    }
+
+    fn set(&self, to: ControlState, from: ControlState) {
+        if let Ok(mut state) = self.state.lock() {
+            if *state == from {
+                *state = to;
+            }
+        }
+        self.changed.notify_all();
+    }
+}
+
+impl Default for JobControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether two jobs work on overlapping folders (the same path, or one inside the other)
+pub fn paths_conflict(a: &[PathBuf], b: &[PathBuf]) -> bool {
+    a.iter()
+        .any(|x| b.iter().any(|y| x.starts_with(y) || y.starts_with(x)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_paths_conflict() {
+        let desktop = vec![PathBuf::from("/home/user/Desktop")];
+        let inside = vec![PathBuf::from("/home/user/Desktop/Photos")];
+        let other = vec![PathBuf::from("/home/user/Documents")];
+
+        assert!(paths_conflict(&desktop, &inside));
+        assert!(paths_conflict(&inside, &desktop));
+        assert!(!paths_conflict(&desktop, &other));
+        // Component-wise: "Desktop2" is not inside "Desktop"
+        assert!(!paths_conflict(&desktop, &[PathBuf::from("/home/user/Desktop2")]));
+    }
+
+    #[test]
+    fn test_pause_blocks_until_resume() {
+        let control = Arc::new(JobControl::new());
+        control.pause();
+
+        let worker = {
+            let control = Arc::clone(&control);
+            thread::spawn(move || control.proceed())
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!worker.is_finished());
+
+        control.resume();
+        assert!(worker.join().unwrap());
+    }
+
+    #[test]
+    fn test_cancel_wakes_paused_job() {
+        let control = Arc::new(JobControl::new());
+        control.pause();
+
+        let worker = {
+            let control = Arc::clone(&control);
+            thread::spawn(move || control.proceed())
+        };
+        control.cancel();
+
+        assert!(!worker.join().unwrap());
+        assert!(control.is_cancelled());
+        // A cancelled job cannot be resumed
+        control.resume();
+        assert!(!control.proceed());
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::database;
+
+/// How walkers treat symbolic links
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkPolicy {
+    /// Leave links out entirely
+    Skip,
+    /// Treat the link itself as an entry (moving it moves the link, not the target)
+    #[default]
+    Link,
+    /// Resolve links to their targets, with loop detection when descending
+    Follow,
+}
+
+impl SymlinkPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymlinkPolicy::Skip => "skip",
+            SymlinkPolicy::Link => "link",
+            SymlinkPolicy::Follow => "follow",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "skip" => SymlinkPolicy::Skip,
+            "follow" => SymlinkPolicy::Follow,
+            _ => SymlinkPolicy::Link,
+        }
+    }
+}
+
+/// How walkers treat FIFOs, sockets and device files
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecialFilePolicy {
+    #[default]
+    Skip,
+    /// Treat the node itself as an entry (never read from it)
+    Include,
+}
+
+impl SpecialFilePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecialFilePolicy::Skip => "skip",
+            SpecialFilePolicy::Include => "include",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "include" => SpecialFilePolicy::Include,
+            _ => SpecialFilePolicy::Skip,
+        }
+    }
+}
+
+/// Symlink and special-file policy shared by every directory walker
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalkPolicy {
+    pub symlinks: SymlinkPolicy,
+    pub special_files: SpecialFilePolicy,
+}
+
+impl WalkPolicy {
+    /// Build the policy from the "symlink_policy" and "special_file_policy" settings
+    pub fn load(db_path: &PathBuf) -> Self {
+        let read = |key: &str| database::get_setting(db_path, key).ok().flatten();
+
+        WalkPolicy {
+            symlinks: read("symlink_policy")
+                .map(|v| SymlinkPolicy::parse(&v))
+                .unwrap_or_default(),
+            special_files: read("special_file_policy")
+                .map(|v| SpecialFilePolicy::parse(&v))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Metadata a walker should use for `path`, or None when the policy skips it.
+    /// Links are described by their own metadata unless followed; broken links
+    /// are skipped when following.
+    pub fn metadata(&self, path: &Path) -> Option<fs::Metadata> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+
+        if metadata.file_type().is_symlink() {
+            return match self.symlinks {
+                SymlinkPolicy::Skip => None,
+                SymlinkPolicy::Link => Some(metadata),
+                SymlinkPolicy::Follow => {
+                    let target = fs::metadata(path).ok()?;
+                    self.admits_type(&target.file_type()).then_some(target)
+                }
+            };
+        }
+
+        self.admits_type(&metadata.file_type()).then_some(metadata)
+    }
+
+    /// Metadata for a file found by listing a folder, or None for folders and entries the
+    /// policy skips. Links to folders are skipped too: they are folder shortcuts, not files.
+    pub fn file_metadata(&self, path: &Path) -> Option<fs::Metadata> {
+        let metadata = self.metadata(path)?;
+        if metadata.is_dir() || (metadata.file_type().is_symlink() && path.is_dir()) {
+            return None;
+        }
+        Some(metadata)
+    }
+
+    /// A WalkDir configured for this policy. When following links, walkdir
+    /// reports link loops as errors, which walkers drop like other unreadable entries.
+    pub fn walk_dir(&self, root: &Path) -> WalkDir {
+        WalkDir::new(root).follow_links(self.symlinks == SymlinkPolicy::Follow)
+    }
+
+    /// Whether an entry produced by `walk_dir` is kept
+    pub fn admits(&self, entry: &walkdir::DirEntry) -> bool {
+        if entry.path_is_symlink() && self.symlinks == SymlinkPolicy::Skip {
+            return false;
+        }
+        self.admits_type(&entry.file_type())
+    }
+
+    fn admits_type(&self, file_type: &fs::FileType) -> bool {
+        !is_special(file_type) || self.special_files == SpecialFilePolicy::Include
+    }
+}
+
+/// FIFOs, sockets and device files
+pub fn is_special(file_type: &fs::FileType) -> bool {
+    !file_type.is_file() && !file_type.is_dir() && !file_type.is_symlink()
+}
+
+/// Target of a symbolic link (None for anything that is not a link)
+pub fn link_target(path: &Path) -> Option<String> {
+    fs::read_link(path)
+        .ok()
+        .map(|target| target.to_string_lossy().to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::tempdir;
+
+    fn policy(symlinks: SymlinkPolicy) -> WalkPolicy {
+        WalkPolicy {
+            symlinks,
+            special_files: SpecialFilePolicy::Skip,
+        }
+    }
+
+    #[test]
+    fn test_symlink_metadata_by_policy() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target.txt");
+        let link = dir.path().join("link.txt");
+        fs::write(&target, b"data").unwrap();
+        symlink(&target, &link).unwrap();
+
+        assert!(policy(SymlinkPolicy::Skip).metadata(&link).is_none());
+        assert!(policy(SymlinkPolicy::Link)
+            .metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(policy(SymlinkPolicy::Follow).metadata(&link).unwrap().is_file());
+        assert_eq!(link_target(&link), Some(target.to_string_lossy().to_string()));
+        assert_eq!(link_target(&target), None);
+    }
+
+    #[test]
+    fn test_links_to_folders_are_not_files() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("projects");
+        let link = dir.path().join("projects-shortcut");
+        fs::create_dir(&folder).unwrap();
+        symlink(&folder, &link).unwrap();
+
+        assert!(policy(SymlinkPolicy::Link).metadata(&link).is_some());
+        assert!(policy(SymlinkPolicy::Link).file_metadata(&link).is_none());
+        assert!(policy(SymlinkPolicy::Follow).file_metadata(&link).is_none());
+        assert!(policy(SymlinkPolicy::Link).file_metadata(&folder).is_none());
+    }
+
+    #[test]
+    fn test_follow_skips_broken_links() {
+        let dir = tempdir().unwrap();
+        let link = dir.path().join("dangling");
+        symlink(dir.path().join("missing"), &link).unwrap();
+
+        assert!(policy(SymlinkPolicy::Follow).metadata(&link).is_none());
+        assert!(policy(SymlinkPolicy::Link).metadata(&link).is_some());
+    }
+
+    #[test]
+    fn test_walk_follow_detects_loops() {
+        let dir = tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("file.txt"), b"x").unwrap();
+        symlink(dir.path(), sub.join("loop")).unwrap();
+
+        let walk = policy(SymlinkPolicy::Follow);
+        let files: Vec<_> = walk
+            .walk_dir(dir.path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| walk.admits(e) && e.file_type().is_file())
+            .collect();
+        assert_eq!(files.len(), 1);
+    }
+}
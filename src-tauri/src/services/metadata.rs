@@ -0,0 +1,268 @@
+use chrono::NaiveDateTime;
+use id3::TagLike;
+use std::collections::HashMap;
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use crate::services::date_source::read_exif_date;
+
+/// Metadata fields templates and rules may query
+pub const METADATA_FIELDS: &[&str] = &[
+    "camera", "make", "model", "lens", "iso", "taken", "artist", "album", "albumartist", "title",
+    "track", "year", "genre", "author", "subject",
+];
+
+const EXIF_EXTENSIONS: &[&str] = &["jpg", "jpeg", "tif", "tiff", "heic", "heif", "png", "webp"];
+const ID3_EXTENSIONS: &[&str] = &["mp3", "aif", "aiff", "wav"];
+
+/// Embedded metadata of one file (EXIF, ID3 or PDF document properties).
+/// Fields a file does not carry are simply absent.
+#[derive(Debug, Clone, Default)]
+pub struct FileMetadata {
+    fields: HashMap<&'static str, String>,
+    /// EXIF DateTimeOriginal, kept as a date so templates can format it
+    pub taken: Option<NaiveDateTime>,
+}
+
+impl FileMetadata {
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.fields.get(field).map(|v| v.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.taken.is_none()
+    }
+
+    fn set(&mut self, field: &'static str, value: impl Into<String>) {
+        let value = clean_value(&value.into());
+        if !value.is_empty() {
+            self.fields.insert(field, value);
+        }
+    }
+}
+
+/// Value used when a file has no such metadata
+pub fn fallback_value(field: &str) -> &'static str {
+    match field {
+        "camera" | "make" | "model" => "Unknown Camera",
+        "lens" => "Unknown Lens",
+        "artist" | "albumartist" => "Unknown Artist",
+        "album" => "Unknown Album",
+        "title" => "Untitled",
+        "author" => "Unknown Author",
+        "track" => "0",
+        _ => "Unknown",
+    }
+}
+
+/// Read the metadata a file carries, chosen by its extension
+pub fn read_metadata(path: &Path) -> FileMetadata {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let mut metadata = FileMetadata::default();
+    if EXIF_EXTENSIONS.contains(&ext.as_str()) {
+        read_exif(path, &mut metadata);
+    } else if ID3_EXTENSIONS.contains(&ext.as_str()) {
+        read_id3(path, &mut metadata);
+    } else if ext == "pdf" {
+        read_pdf(path, &mut metadata);
+    }
+    metadata
+}
+
+/// Reads each file's metadata at most once during a batch
+#[derive(Default)]
+pub struct MetadataCache {
+    entries: HashMap<PathBuf, FileMetadata>,
+}
+
+impl MetadataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&mut self, path: &Path) -> &FileMetadata {
+        self.entries
+            .entry(path.to_path_buf())
+            .or_insert_with(|| read_metadata(path))
+    }
+}
+
+fn read_exif(path: &Path, metadata: &mut FileMetadata) {
+    let Ok(file) = fs::File::open(path) else {
+        return;
+    };
+    let mut reader = BufReader::new(file);
+    let Ok(exif) = exif::Reader::new().read_from_container(&mut reader) else {
+        return;
+    };
+
+    let ascii = |tag: exif::Tag| -> Option<String> {
+        match &exif.get_field(tag, exif::In::PRIMARY)?.value {
+            exif::Value::Ascii(values) => {
+                Some(String::from_utf8_lossy(values.first()?).to_string())
+            }
+            _ => None,
+        }
+    };
+
+    let make = ascii(exif::Tag::Make).unwrap_or_default();
+    let model = ascii(exif::Tag::Model).unwrap_or_default();
+    metadata.set("make", make.as_str());
+    metadata.set("model", model.as_str());
+
+    // Most models already start with the maker ("Canon EOS R5")
+    let camera = if model.trim().is_empty() || model.starts_with(make.trim()) {
+        model.clone()
+    } else {
+        format!("{} {}", make.trim(), model.trim())
+    };
+    metadata.set("camera", camera);
+
+    if let Some(lens) = ascii(exif::Tag::LensModel) {
+        metadata.set("lens", lens);
+    }
+    if let Some(iso) = exif
+        .get_field(exif::Tag::PhotographicSensitivity, exif::In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+    {
+        metadata.set("iso", iso.to_string());
+    }
+
+    metadata.taken = read_exif_date(path);
+}
+
+fn read_id3(path: &Path, metadata: &mut FileMetadata) {
+    let Ok(tag) = id3::v1v2::read_from_path(path) else {
+        return;
+    };
+
+    if let Some(artist) = tag.artist() {
+        metadata.set("artist", artist);
+    }
+    if let Some(album_artist) = tag.album_artist() {
+        metadata.set("albumartist", album_artist);
+    }
+    if let Some(album) = tag.album() {
+        metadata.set("album", album);
+    }
+    if let Some(title) = tag.title() {
+        metadata.set("title", title);
+    }
+    if let Some(track) = tag.track() {
+        metadata.set("track", track.to_string());
+    }
+    if let Some(year) = tag.year().or_else(|| tag.date_recorded().map(|d| d.year)) {
+        metadata.set("year", year.to_string());
+    }
+    if let Some(genre) = tag.genre_parsed() {
+        metadata.set("genre", genre.as_ref());
+    }
+}
+
+fn read_pdf(path: &Path, metadata: &mut FileMetadata) {
+    let Ok(document) = lopdf::Document::load(path) else {
+        return;
+    };
+    let Some(info) = document
+        .trailer
+        .get(b"Info")
+        .ok()
+        .and_then(|obj| document.dereference(obj).ok())
+        .and_then(|(_, obj)| obj.as_dict().ok())
+    else {
+        return;
+    };
+
+    for (key, field) in [
+        (&b"Title"[..], "title"),
+        (&b"Author"[..], "author"),
+        (&b"Subject"[..], "subject"),
+    ] {
+        if let Some(value) = info
+            .get(key)
+            .ok()
+            .and_then(|obj| lopdf::decode_text_string(obj).ok())
+        {
+            metadata.set(field, value);
+        }
+    }
+
+    if let Some(year) = info
+        .get(b"CreationDate")
+        .ok()
+        .and_then(|obj| obj.as_str().ok())
+        .and_then(|date| String::from_utf8_lossy(date).get(2..6).map(str::to_string))
+        .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+    {
+        metadata.set("year", year);
+    }
+}
+
+/// Trim padding and replace characters that cannot appear in a file name
+fn clean_value(value: &str) -> String {
+    value
+        .trim_matches(|c: char| c.is_whitespace() || c == '\0')
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' => '_',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_no_metadata_uses_fallbacks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("song.mp3");
+        fs::write(&path, b"not really audio").unwrap();
+
+        let metadata = read_metadata(&path);
+        assert!(metadata.is_empty());
+        assert_eq!(metadata.get("artist"), None);
+        assert_eq!(fallback_value("artist"), "Unknown Artist");
+        assert_eq!(fallback_value("camera"), "Unknown Camera");
+    }
+
+    #[test]
+    fn test_read_id3_tags() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("track.mp3");
+        fs::write(&path, b"").unwrap();
+
+        let mut tag = id3::Tag::new();
+        tag.set_artist("AC/DC");
+        tag.set_album("Back in Black");
+        tag.set_track(6);
+        tag.write_to_path(&path, id3::Version::Id3v24).unwrap();
+
+        let metadata = read_metadata(&path);
+        assert_eq!(metadata.get("artist"), Some("AC_DC"));
+        assert_eq!(metadata.get("album"), Some("Back in Black"));
+        assert_eq!(metadata.get("track"), Some("6"));
+        assert_eq!(metadata.get("title"), None);
+    }
+
+    #[test]
+    fn test_cache_reads_once() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("doc.pdf");
+        fs::write(&path, b"%PDF-broken").unwrap();
+
+        let mut cache = MetadataCache::new();
+        assert!(cache.get(&path).is_empty());
+        fs::remove_file(&path).unwrap();
+        assert!(cache.get(&path).is_empty());
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
@@ -1,2 +1,18 @@
+pub mod archive;
+pub mod batch_rename;
 pub mod classifier;
+pub mod date_source;
+pub mod extension;
+pub mod filename;
+pub mod hasher;
+pub mod in_use;
+pub mod jobs;
+pub mod link_policy;
+pub mod metadata;
+pub mod mover;
+pub mod rename_template;
+pub mod sequence;
+pub mod settle;
+pub mod shred;
+pub mod trash_bin;
 pub mod watcher;
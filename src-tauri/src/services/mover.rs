@@ -0,0 +1,380 @@
+use filetime::FileTime;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::services::hasher::files_identical;
+use crate::services::link_policy::is_special;
+
+/// Move a file. When a plain rename is not possible (e.g. across devices) the file
+/// is copied, verified and only then removed from its original location.
+/// Directories are moved file by file in that case, never replacing existing files.
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    if is_real_dir(from) {
+        let result = move_tree(from, to, &mut |_| Ok(None), &mut |_, _, _| {})?;
+        return match result.errors.first() {
+            Some((path, e)) => Err(io::Error::other(format!("{}: {}", path.display(), e))),
+            None if !result.skipped.is_empty() => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} files already exist at the destination", result.skipped.len()),
+            )),
+            None => Ok(()),
+        };
+    }
+
+    copy_file_verified(from, to)?;
+
+    // The verified copy stays in place if the original cannot be removed,
+    // so a failure here never loses data
+    fs::remove_file(from)
+}
+
+/// Copy a file and verify size and content before it appears at `to`.
+/// Timestamps, permissions and extended attributes are kept where possible.
+pub fn copy_file_verified(from: &Path, to: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(from)?;
+
+    if metadata.file_type().is_symlink() {
+        return copy_symlink(from, to);
+    }
+
+    // Copy next to the destination first so a failed copy never replaces an existing file
+    let temp_path = temp_path_for(to);
+    if let Err(e) = fs::copy(from, &temp_path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    match files_identical(from, &temp_path) {
+        Ok(true) => {}
+        Ok(false) => {
+            let _ = fs::remove_file(&temp_path);
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("copy verification failed: {}", from.display()),
+            ));
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+    }
+
+    preserve_metadata(from, &temp_path, &metadata);
+
+    if let Err(e) = fs::rename(&temp_path, to) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// What happened while copying or moving a directory tree
+#[derive(Debug, Default)]
+pub struct TreeTransfer {
+    /// The whole tree was moved with a single rename
+    pub renamed: bool,
+    /// Files and links written, as (source, destination)
+    pub files: Vec<(PathBuf, PathBuf)>,
+    /// Directories that did not exist at the destination before, parents first
+    pub created_dirs: Vec<PathBuf>,
+    /// Source directories removed after a move emptied them, children first
+    pub removed_dirs: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+/// Decides where a file goes when its destination already exists (None skips it)
+pub type CollisionResolver<'a> = dyn FnMut(&Path) -> io::Result<Option<PathBuf>> + 'a;
+
+/// Called after each file with (files done, total files, source path)
+pub type TreeProgress<'a> = dyn FnMut(usize, usize, &Path) + 'a;
+
+/// Whether `path` is a directory itself rather than a link to one
+pub fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path).map(|m| m.is_dir()).unwrap_or(false)
+}
+
+/// Copy a directory tree into `to`, merging with whatever is already there.
+/// Every file is copied verified; links are recreated and special files skipped.
+pub fn copy_tree(
+    from: &Path,
+    to: &Path,
+    resolve: &mut CollisionResolver,
+    progress: &mut TreeProgress,
+) -> io::Result<TreeTransfer> {
+    transfer_tree(from, to, false, resolve, progress)
+}
+
+/// Move a directory tree. A single rename is used when `to` does not exist yet;
+/// otherwise (another device, or merging into an existing folder) files are moved
+/// one by one and source folders left empty are removed.
+pub fn move_tree(
+    from: &Path,
+    to: &Path,
+    resolve: &mut CollisionResolver,
+    progress: &mut TreeProgress,
+) -> io::Result<TreeTransfer> {
+    if fs::symlink_metadata(to).is_err() && fs::rename(from, to).is_ok() {
+        progress(1, 1, from);
+        return Ok(TreeTransfer {
+            renamed: true,
+            ..TreeTransfer::default()
+        });
+    }
+
+    transfer_tree(from, to, true, resolve, progress)
+}
+
+fn transfer_tree(
+    from: &Path,
+    to: &Path,
+    remove_source: bool,
+    resolve: &mut CollisionResolver,
+    progress: &mut TreeProgress,
+) -> io::Result<TreeTransfer> {
+    if to.starts_with(from) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot copy a folder into itself: {}", from.display()),
+        ));
+    }
+
+    let mut result = TreeTransfer::default();
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(from).follow_links(false) {
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                let path = e.path().map(Path::to_path_buf).unwrap_or_else(|| from.to_path_buf());
+                result.errors.push((path, e.to_string()));
+            }
+        }
+    }
+
+    let total = entries.iter().filter(|e| !e.file_type().is_dir()).count();
+    let mut done = 0;
+    let mut dirs: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+    for entry in &entries {
+        let source = entry.path();
+        let relative = source.strip_prefix(from).unwrap_or(source);
+        let dest = to.join(relative);
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            if !dest.exists() {
+                match fs::create_dir_all(&dest) {
+                    Ok(()) => result.created_dirs.push(dest.clone()),
+                    Err(e) => result.errors.push((source.to_path_buf(), e.to_string())),
+                }
+            }
+            dirs.push((source.to_path_buf(), dest));
+            continue;
+        }
+
+        done += 1;
+
+        if is_special(&file_type) {
+            // FIFOs, sockets and devices have no content to copy
+            result.skipped.push(source.to_path_buf());
+            progress(done, total, source);
+            continue;
+        }
+
+        let target = if fs::symlink_metadata(&dest).is_ok() {
+            match resolve(&dest) {
+                Ok(target) => target,
+                Err(e) => {
+                    result.errors.push((source.to_path_buf(), e.to_string()));
+                    progress(done, total, source);
+                    continue;
+                }
+            }
+        } else {
+            Some(dest)
+        };
+
+        match target {
+            Some(target) => {
+                let outcome = if remove_source {
+                    move_file(source, &target)
+                } else {
+                    copy_file_verified(source, &target)
+                };
+                match outcome {
+                    Ok(()) => result.files.push((source.to_path_buf(), target)),
+                    Err(e) => result.errors.push((source.to_path_buf(), e.to_string())),
+                }
+            }
+            None => result.skipped.push(source.to_path_buf()),
+        }
+
+        progress(done, total, source);
+    }
+
+    // Deepest folders first: writing files changed their times, and a source
+    // folder can only be removed once its children are gone
+    for (source, dest) in dirs.iter().rev() {
+        if result.created_dirs.contains(dest) {
+            if let Ok(metadata) = fs::metadata(source) {
+                preserve_metadata(source, dest, &metadata);
+            }
+        }
+        if remove_source && fs::remove_dir(source).is_ok() {
+            result.removed_dirs.push(source.clone());
+        }
+    }
+
+    Ok(result)
+}
+
+/// Copy permissions, access/modification times and extended attributes (best effort)
+fn preserve_metadata(from: &Path, to: &Path, metadata: &fs::Metadata) {
+    copy_xattrs(from, to);
+
+    let _ = filetime::set_file_times(
+        to,
+        FileTime::from_last_access_time(metadata),
+        FileTime::from_last_modification_time(metadata),
+    );
+
+    // Permissions last, a read-only mode would block the steps above
+    let _ = fs::set_permissions(to, metadata.permissions());
+}
+
+#[cfg(unix)]
+fn copy_xattrs(from: &Path, to: &Path) {
+    if let Ok(names) = xattr::list(from) {
+        for name in names {
+            if let Ok(Some(value)) = xattr::get(from, &name) {
+                let _ = xattr::set(to, &name, &value);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn copy_xattrs(_from: &Path, _to: &Path) {}
+
+/// Recreate a symbolic link instead of copying what it points to
+#[cfg(unix)]
+pub fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    let target = fs::read_link(from)?;
+    std::os::unix::fs::symlink(target, to)
+}
+
+#[cfg(not(unix))]
+pub fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    fs::copy(from, to).map(|_| ())
+}
+
+fn temp_path_for(to: &Path) -> PathBuf {
+    let file_name = to
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    to.with_file_name(format!(".{}.moving", file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_move_file_same_device() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("a.txt");
+        let to = dir.path().join("b.txt");
+        fs::write(&from, b"hello").unwrap();
+
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(fs::read(&to).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_copy_verified_keeps_mtime() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("old.txt");
+        let to = dir.path().join("copy.txt");
+        fs::write(&from, b"content").unwrap();
+
+        let mtime = FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(&from, mtime).unwrap();
+
+        copy_file_verified(&from, &to).unwrap();
+        let copied = fs::metadata(&to).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&copied), mtime);
+        assert!(!temp_path_for(&to).exists());
+    }
+
+    #[test]
+    fn test_copy_tree_merges_and_resolves_collisions() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("src");
+        let to = dir.path().join("dest");
+        fs::create_dir_all(from.join("sub")).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        fs::write(from.join("a.txt"), b"a").unwrap();
+        fs::write(from.join("sub/b.txt"), b"b").unwrap();
+        fs::write(to.join("a.txt"), b"existing").unwrap();
+
+        let mut calls = 0;
+        let result = copy_tree(&from, &to, &mut |_| Ok(None), &mut |_, _, _| calls += 1).unwrap();
+
+        assert_eq!(calls, 2);
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.skipped, vec![from.join("a.txt")]);
+        assert_eq!(result.created_dirs, vec![to.join("sub")]);
+        assert_eq!(fs::read(to.join("a.txt")).unwrap(), b"existing");
+        assert_eq!(fs::read(to.join("sub/b.txt")).unwrap(), b"b");
+        assert!(from.join("sub/b.txt").exists());
+    }
+
+    #[test]
+    fn test_move_tree_into_existing_folder() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("src");
+        let to = dir.path().join("dest");
+        fs::create_dir_all(from.join("sub")).unwrap();
+        fs::create_dir_all(to.join("sub")).unwrap();
+        fs::write(from.join("sub/b.txt"), b"b").unwrap();
+
+        let result = move_tree(&from, &to, &mut |_| Ok(None), &mut |_, _, _| {}).unwrap();
+
+        assert!(!result.renamed);
+        assert!(result.errors.is_empty());
+        assert_eq!(fs::read(to.join("sub/b.txt")).unwrap(), b"b");
+        assert!(!from.exists());
+    }
+
+    #[test]
+    fn test_copy_tree_into_itself_fails() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("src");
+        fs::create_dir_all(&from).unwrap();
+
+        let result = copy_tree(&from, &from.join("inner"), &mut |_| Ok(None), &mut |_, _, _| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_copy_verified_replaces_existing() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("new.txt");
+        let to = dir.path().join("existing.txt");
+        fs::write(&from, b"new").unwrap();
+        fs::write(&to, b"old").unwrap();
+
+        copy_file_verified(&from, &to).unwrap();
+        assert_eq!(fs::read(&to).unwrap(), b"new");
+    }
+}
@@ -0,0 +1,451 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
+use std::fs;
+use std::path::Path;
+
+use crate::services::classifier::{classify_extension, get_category_folder};
+use crate::services::date_source::{resolve_file_date, DateSource};
+use crate::services::hasher::hash_file;
+use crate::services::metadata::{fallback_value, FileMetadata, METADATA_FIELDS};
+
+/// Tokens a rename template may use
+const TOKENS: &[&str] = &[
+    "name", "ext", "original", "n", "total", "date", "created", "modified", "now", "parent", "category",
+    "size", "hash",
+];
+
+/// Tokens whose spec is a strftime date format
+const DATE_TOKENS: &[&str] = &["date", "created", "modified", "now", "taken"];
+
+/// Filters that may also be written as `{token:filter}`
+const SHORTHAND_FILTERS: &[&str] = &["lower", "upper", "title", "trim", "slug"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Lower,
+    Upper,
+    Title,
+    Trim,
+    Slug,
+    Replace(String, String),
+    Truncate(usize),
+    Default(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Token {
+        name: String,
+        spec: Option<String>,
+        filters: Vec<Filter>,
+    },
+}
+
+/// Values a template is rendered against for one file
+pub struct TemplateContext<'a> {
+    pub path: &'a Path,
+    /// Current name without extension (after earlier rules)
+    pub stem: &'a str,
+    /// Current extension including the dot, empty when there is none
+    pub extension: &'a str,
+    pub counter: i64,
+    /// Number of files sharing the counter, for `{total}`
+    pub total: usize,
+    pub date_source: DateSource,
+    /// Embedded metadata, needed only when the template uses metadata tokens
+    pub metadata: Option<&'a FileMetadata>,
+}
+
+/// A parsed rename template such as `{date:%Y-%m-%d}_{name:slug}_{n:03}{ext:lower}`.
+///
+/// Tokens: name, ext, original, n, total, date, created, modified, now, parent, category, size, hash,
+/// plus the metadata fields camera, make, model, lens, iso, taken, artist, album, albumartist,
+/// title, track, year, genre, author and subject (e.g. "Unknown Artist" when missing).
+/// Filters follow `|` (or a single `:` shorthand): lower, upper, title, trim, slug,
+/// replace:from:to, truncate:N and default:text. `{{` and `}}` are literal braces.
+#[derive(Debug, Clone)]
+pub struct RenameTemplate {
+    parts: Vec<Part>,
+}
+
+impl RenameTemplate {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut content = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        content.push(c);
+                    }
+                    if !closed {
+                        return Err("템플릿 오류: 닫히지 않은 '{'가 있습니다".to_string());
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_token(&content)?);
+                }
+                '}' => return Err("템플릿 오류: 짝이 맞지 않는 '}'가 있습니다".to_string()),
+                _ => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(RenameTemplate { parts })
+    }
+
+    /// Whether the template uses the `{n}` counter or `{total}`
+    pub fn uses_counter(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|p| matches!(p, Part::Token { name, .. } if name == "n" || name == "total"))
+    }
+
+    /// Whether the template needs the file's embedded metadata
+    pub fn uses_metadata(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|p| matches!(p, Part::Token { name, .. } if METADATA_FIELDS.contains(&name.as_str())))
+    }
+
+    pub fn render(&self, ctx: &TemplateContext) -> String {
+        let metadata = fs::metadata(ctx.path).ok();
+        let mut output = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => output.push_str(text),
+                Part::Token {
+                    name,
+                    spec,
+                    filters,
+                } => {
+                    let value = token_value(name, spec.as_deref(), ctx, metadata.as_ref());
+                    let value = filters.iter().fold(value, |v, f| apply_filter(&v, f));
+                    if value.is_empty() && METADATA_FIELDS.contains(&name.as_str()) {
+                        output.push_str(fallback_value(name));
+                    } else {
+                        output.push_str(&value);
+                    }
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn parse_token(content: &str) -> Result<Part, String> {
+    let mut segments = content.split('|');
+    let head = segments.next().unwrap_or("").trim();
+
+    let (name, spec) = match head.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec.to_string())),
+        None => (head, None),
+    };
+
+    if !TOKENS.contains(&name) && !METADATA_FIELDS.contains(&name) {
+        return Err(format!("템플릿 오류: 알 수 없는 토큰 '{}'", name));
+    }
+
+    let mut filters = Vec::new();
+
+    // `{name:slug}` is shorthand for `{name|slug}`
+    let spec = match spec {
+        Some(s) if SHORTHAND_FILTERS.contains(&s.as_str()) => {
+            filters.push(parse_filter(&s)?);
+            None
+        }
+        other => other,
+    };
+
+    if let Some(format) = spec.as_deref().filter(|_| DATE_TOKENS.contains(&name)) {
+        if !is_valid_date_format(format) {
+            return Err(format!("템플릿 오류: 잘못된 날짜 형식 '{}'", format));
+        }
+    }
+
+    for segment in segments {
+        filters.push(parse_filter(segment.trim())?);
+    }
+
+    Ok(Part::Token {
+        name: name.to_string(),
+        spec,
+        filters,
+    })
+}
+
+/// Whether chrono can render a strftime format; rendering an invalid one panics
+pub fn is_valid_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+fn parse_filter(text: &str) -> Result<Filter, String> {
+    let mut args = text.split(':');
+    let name = args.next().unwrap_or("");
+
+    match name {
+        "lower" => Ok(Filter::Lower),
+        "upper" => Ok(Filter::Upper),
+        "title" => Ok(Filter::Title),
+        "trim" => Ok(Filter::Trim),
+        "slug" => Ok(Filter::Slug),
+        "replace" => {
+            let from = args.next().unwrap_or("");
+            if from.is_empty() {
+                return Err("템플릿 오류: replace 필터에는 찾을 문자열이 필요합니다".to_string());
+            }
+            Ok(Filter::Replace(
+                from.to_string(),
+                args.next().unwrap_or("").to_string(),
+            ))
+        }
+        "truncate" => args
+            .next()
+            .and_then(|n| n.parse().ok())
+            .map(Filter::Truncate)
+            .ok_or_else(|| "템플릿 오류: truncate 필터에는 길이가 필요합니다".to_string()),
+        // Everything after "default:" so the text itself may contain ':'
+        "default" => Ok(Filter::Default(
+            text.split_once(':').map(|(_, v)| v).unwrap_or("").to_string(),
+        )),
+        _ => Err(format!("템플릿 오류: 알 수 없는 필터 '{}'", name)),
+    }
+}
+
+fn token_value(
+    name: &str,
+    spec: Option<&str>,
+    ctx: &TemplateContext,
+    metadata: Option<&fs::Metadata>,
+) -> String {
+    let date_format = spec.unwrap_or("%Y%m%d");
+
+    match name {
+        "name" => ctx.stem.to_string(),
+        "ext" => ctx.extension.to_string(),
+        "original" => ctx
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        "n" => {
+            let width = spec.and_then(|s| s.parse::<usize>().ok()).unwrap_or(1);
+            format!("{:0width$}", ctx.counter, width = width)
+        }
+        "total" => ctx.total.to_string(),
+        "date" | "created" | "modified" => {
+            let source = match name {
+                "created" => DateSource::Created,
+                "modified" => DateSource::Modified,
+                _ => ctx.date_source,
+            };
+            resolve_file_date(ctx.path, metadata, source)
+                .map(|resolved| resolved.datetime.format(date_format).to_string())
+                .unwrap_or_else(|| "Unknown".to_string())
+        }
+        "now" => Local::now().format(date_format).to_string(),
+        "parent" => ctx
+            .path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        "category" => {
+            let category = classify_extension(&ctx.extension.to_lowercase());
+            get_category_folder(&category).to_string()
+        }
+        "size" => {
+            let size = metadata.map(|m| m.len()).unwrap_or(0);
+            if spec == Some("b") {
+                size.to_string()
+            } else {
+                format_size(size)
+            }
+        }
+        "hash" => {
+            let length = spec.and_then(|s| s.parse::<usize>().ok()).unwrap_or(8);
+            hash_file(ctx.path)
+                .map(|hash| hash.chars().take(length).collect())
+                .unwrap_or_default()
+        }
+        "taken" => ctx
+            .metadata
+            .and_then(|m| m.taken)
+            .map(|taken| taken.format(date_format).to_string())
+            .unwrap_or_default(),
+        "track" => match ctx.metadata.and_then(|m| m.get("track")) {
+            Some(track) => {
+                let width = spec.and_then(|s| s.parse::<usize>().ok()).unwrap_or(1);
+                format!("{:0>width$}", track, width = width)
+            }
+            None => String::new(),
+        },
+        field if METADATA_FIELDS.contains(&field) => ctx
+            .metadata
+            .and_then(|m| m.get(field))
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn apply_filter(value: &str, filter: &Filter) -> String {
+    match filter {
+        Filter::Lower => value.to_lowercase(),
+        Filter::Upper => value.to_uppercase(),
+        Filter::Title => title_case(value),
+        Filter::Trim => value.trim().to_string(),
+        Filter::Slug => slugify(value),
+        Filter::Replace(from, to) => value.replace(from.as_str(), to),
+        Filter::Truncate(length) => value.chars().take(*length).collect(),
+        Filter::Default(text) if value.is_empty() => text.clone(),
+        Filter::Default(_) => value.to_string(),
+    }
+}
+
+/// Capitalize the first letter of each whitespace-separated word
+pub fn title_case(value: &str) -> String {
+    value
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_uppercase().collect::<String>() + chars.as_str().to_lowercase().as_str()
+                }
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lowercase and join runs of letters/digits with '-' (non-ASCII letters are kept)
+fn slugify(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn format_size(size: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if size >= GB {
+        format!("{:.1}GB", size as f64 / GB as f64)
+    } else if size >= MB {
+        format!("{:.1}MB", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.1}KB", size as f64 / KB as f64)
+    } else {
+        format!("{}B", size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn render(template: &str, path: &Path, counter: i64) -> String {
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        let extension = path
+            .extension()
+            .map(|e| format!(".{}", e.to_str().unwrap()))
+            .unwrap_or_default();
+
+        RenameTemplate::parse(template).unwrap().render(&TemplateContext {
+            path,
+            stem,
+            extension: &extension,
+            counter,
+            total: 12,
+            date_source: DateSource::Filename,
+            metadata: None,
+        })
+    }
+
+    #[test]
+    fn test_render_tokens_and_filters() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("IMG_20240315_101010 My Photo.JPG");
+        fs::write(&path, b"data").unwrap();
+
+        assert_eq!(
+            render("{date:%Y-%m-%d}_{name:slug}_{n:03}{ext:lower}", &path, 7),
+            "2024-03-15_img-20240315-101010-my-photo_007.jpg"
+        );
+        assert_eq!(render("{category}_{size:b}", &path, 1), "Images_4");
+        assert_eq!(
+            render("{name|replace:IMG_:|truncate:8|upper}", &path, 1),
+            "20240315"
+        );
+        assert_eq!(render("{{{n}}}", &path, 2), "{2}");
+        assert_eq!(render("{n:02} of {total}", &path, 3), "03 of 12");
+    }
+
+    #[test]
+    fn test_metadata_tokens_fall_back() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("song.mp3");
+        fs::write(&path, b"").unwrap();
+
+        let template = RenameTemplate::parse("{artist} - {title|default:Track}").unwrap();
+        assert!(template.uses_metadata());
+        assert_eq!(render("{artist} - {album}", &path, 1), "Unknown Artist - Unknown Album");
+        assert_eq!(render("{camera|default:Phone}_{taken:%Y}", &path, 1), "Phone_Unknown");
+        assert!(!RenameTemplate::parse("{name}").unwrap().uses_metadata());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(RenameTemplate::parse("{unknown}").is_err());
+        assert!(RenameTemplate::parse("{name|shout}").is_err());
+        assert!(RenameTemplate::parse("{name").is_err());
+        assert!(RenameTemplate::parse("name}").is_err());
+        assert!(RenameTemplate::parse("{name|truncate:x}").is_err());
+        assert!(RenameTemplate::parse("{date:%Q}").is_err());
+        assert!(RenameTemplate::parse("{taken:%Y-%}").is_err());
+        assert!(RenameTemplate::parse("{now:%Y-%m-%d}").is_ok());
+    }
+
+    #[test]
+    fn test_uses_counter() {
+        assert!(RenameTemplate::parse("{name}_{n:02}").unwrap().uses_counter());
+        assert!(!RenameTemplate::parse("{name}{ext}").unwrap().uses_counter());
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("  Hello, World!  "), "hello-world");
+        assert_eq!(slugify("회의록 최종본 (2)"), "회의록-최종본-2");
+    }
+}
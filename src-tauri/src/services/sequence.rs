@@ -0,0 +1,260 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::services::date_source::{resolve_file_date, DateSource};
+use crate::services::filename::to_nfc;
+
+/// Order in which files are numbered
+#[derive(Debug, Clone, PartialEq)]
+pub enum SequenceSort {
+    /// The order the files were given in
+    Input,
+    /// Natural name order ("img2" before "img10")
+    Name,
+    /// Resolved date, using the date source's fallback chain (EXIF falls back to file times)
+    Date(DateSource),
+    Size,
+    /// Paths in the given order first, remaining files after them in input order
+    Custom(Vec<String>),
+}
+
+/// Which files share a counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterScope {
+    Global,
+    Folder,
+    Extension,
+}
+
+impl CounterScope {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "folder" => CounterScope::Folder,
+            "extension" => CounterScope::Extension,
+            _ => CounterScope::Global,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SequenceOptions {
+    pub sort: SequenceSort,
+    pub descending: bool,
+    pub scope: CounterScope,
+    pub start: i64,
+    pub step: i64,
+    /// Added to every number, e.g. to continue an earlier batch
+    pub offset: i64,
+}
+
+impl Default for SequenceOptions {
+    fn default() -> Self {
+        SequenceOptions {
+            sort: SequenceSort::Input,
+            descending: false,
+            scope: CounterScope::Global,
+            start: 1,
+            step: 1,
+            offset: 0,
+        }
+    }
+}
+
+/// Number assigned to one file and the size of its counter group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceNumber {
+    pub number: i64,
+    pub total: usize,
+}
+
+/// Number files, returned in the order of `paths`
+pub fn number_files(paths: &[PathBuf], options: &SequenceOptions) -> Vec<SequenceNumber> {
+    let mut order: Vec<usize> = (0..paths.len()).collect();
+
+    match &options.sort {
+        SequenceSort::Input => {}
+        SequenceSort::Name => {
+            let names: Vec<String> = paths
+                .iter()
+                .map(|p| {
+                    let name = p.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+                    to_nfc(&name).to_lowercase()
+                })
+                .collect();
+            order.sort_by(|&a, &b| natural_cmp(&names[a], &names[b]));
+        }
+        SequenceSort::Date(source) => {
+            let dates: Vec<_> = paths
+                .iter()
+                .map(|p| {
+                    let metadata = fs::metadata(p).ok();
+                    resolve_file_date(p, metadata.as_ref(), *source).map(|r| r.datetime)
+                })
+                .collect();
+            // Files without a date go last
+            order.sort_by(|&a, &b| match (&dates[a], &dates[b]) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+        SequenceSort::Size => {
+            let sizes: Vec<u64> = paths
+                .iter()
+                .map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0))
+                .collect();
+            order.sort_by_key(|&i| sizes[i]);
+        }
+        SequenceSort::Custom(custom) => {
+            let positions: HashMap<&str, usize> = custom
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (p.as_str(), i))
+                .collect();
+            order.sort_by_key(|&i| {
+                positions
+                    .get(paths[i].to_string_lossy().as_ref())
+                    .copied()
+                    .unwrap_or(usize::MAX)
+            });
+        }
+    }
+
+    if options.descending {
+        order.reverse();
+    }
+
+    let groups: Vec<String> = paths
+        .iter()
+        .map(|p| match options.scope {
+            CounterScope::Global => String::new(),
+            CounterScope::Folder => p
+                .parent()
+                .map(|d| to_nfc(&d.to_string_lossy()))
+                .unwrap_or_default(),
+            CounterScope::Extension => p
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let mut group_sizes: HashMap<&str, usize> = HashMap::new();
+    for group in &groups {
+        *group_sizes.entry(group.as_str()).or_insert(0) += 1;
+    }
+
+    let mut positions: HashMap<&str, i64> = HashMap::new();
+    let mut numbers = vec![SequenceNumber { number: 0, total: 0 }; paths.len()];
+
+    for i in order {
+        let group = groups[i].as_str();
+        let position = positions.entry(group).or_insert(0);
+        numbers[i] = SequenceNumber {
+            number: options.start + options.offset + *position * options.step,
+            total: group_sizes[group],
+        };
+        *position += 1;
+    }
+
+    numbers
+}
+
+/// Compare strings treating runs of ASCII digits as numbers ("file2" < "file10")
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x_digits = take_digits(&mut a_chars);
+                let y_digits = take_digits(&mut b_chars);
+                let x_trimmed = x_digits.trim_start_matches('0');
+                let y_trimmed = y_digits.trim_start_matches('0');
+
+                let ordering = x_trimmed
+                    .len()
+                    .cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed))
+                    .then_with(|| x_digits.len().cmp(&y_digits.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| c.is_ascii_digit()) {
+        digits.push(c);
+        chars.next();
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec!["img10.jpg", "img2.jpg", "img1.jpg", "IMG02.jpg"];
+        names.sort_by(|a, b| natural_cmp(&a.to_lowercase(), &b.to_lowercase()));
+        assert_eq!(names, vec!["img1.jpg", "img2.jpg", "IMG02.jpg", "img10.jpg"]);
+    }
+
+    #[test]
+    fn test_number_by_name_per_folder() {
+        let paths: Vec<PathBuf> = ["a/x10.jpg", "b/y.png", "a/x2.jpg", "a/x1.jpg"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let options = SequenceOptions {
+            sort: SequenceSort::Name,
+            scope: CounterScope::Folder,
+            start: 1,
+            step: 10,
+            offset: 5,
+            ..SequenceOptions::default()
+        };
+
+        let numbers: Vec<(i64, usize)> = number_files(&paths, &options)
+            .iter()
+            .map(|n| (n.number, n.total))
+            .collect();
+        assert_eq!(numbers, vec![(26, 3), (6, 1), (16, 3), (6, 3)]);
+    }
+
+    #[test]
+    fn test_custom_order_descending() {
+        let paths: Vec<PathBuf> = ["a", "b", "c"].iter().map(PathBuf::from).collect();
+        let options = SequenceOptions {
+            sort: SequenceSort::Custom(vec!["c".to_string(), "a".to_string()]),
+            descending: true,
+            ..SequenceOptions::default()
+        };
+
+        let numbers: Vec<i64> = number_files(&paths, &options)
+            .iter()
+            .map(|n| n.number)
+            .collect();
+        // c, a, b reversed: b=1, a=2, c=3
+        assert_eq!(numbers, vec![2, 1, 3]);
+    }
+}
@@ -0,0 +1,310 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::database;
+use crate::services::in_use::{self, OpenFiles};
+
+/// Extensions browsers and download managers use for incomplete downloads
+pub const PARTIAL_DOWNLOAD_EXTENSIONS: &[&str] = &[
+    ".crdownload",
+    ".part",
+    ".partial",
+    ".download",
+    ".opdownload",
+    ".!ut",
+];
+
+pub const DEFAULT_SETTLE_SECONDS: u64 = 5;
+pub const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 500;
+
+/// Files modified within this window get a second size sample
+const ACTIVE_WINDOW: Duration = Duration::from_secs(120);
+
+/// Why a file was left in place for now
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DeferReason {
+    PartialDownload,
+    RecentlyModified,
+    SizeChanging,
+    /// Open in another app
+    InUse,
+    /// Locked by another app
+    Locked,
+}
+
+impl DeferReason {
+    pub fn korean_label(&self) -> &'static str {
+        match self {
+            DeferReason::PartialDownload => "다운로드 중",
+            DeferReason::RecentlyModified => "최근 수정됨",
+            DeferReason::SizeChanging => "쓰기 진행 중",
+            DeferReason::InUse => "다른 앱에서 사용 중",
+            DeferReason::Locked => "잠김",
+        }
+    }
+}
+
+/// A file that was deferred instead of moved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredFile {
+    pub path: String,
+    pub reason: DeferReason,
+}
+
+pub fn is_partial_download(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e.to_lowercase()))
+        .unwrap_or_default();
+
+    if PARTIAL_DOWNLOAD_EXTENSIONS.contains(&extension.as_str()) {
+        return true;
+    }
+
+    // Firefox keeps a placeholder with the final name next to "<name>.part"
+    let file_name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => name,
+        None => return false,
+    };
+    let parent = path.parent().unwrap_or(Path::new(""));
+    PARTIAL_DOWNLOAD_EXTENSIONS
+        .iter()
+        .any(|ext| parent.join(format!("{}{}", file_name, ext)).exists())
+}
+
+/// Detects files that are not ready to be moved yet
+pub struct SettleChecker {
+    settle_window: Duration,
+    sample_interval: Duration,
+    check_in_use: bool,
+}
+
+impl SettleChecker {
+    pub fn new(settle_seconds: u64, sample_interval_ms: u64) -> Self {
+        SettleChecker {
+            settle_window: Duration::from_secs(settle_seconds),
+            sample_interval: Duration::from_millis(sample_interval_ms),
+            check_in_use: true,
+        }
+    }
+
+    /// Build a checker from the "settle_seconds", "settle_sample_ms" and
+    /// "check_in_use" settings
+    pub fn load(db_path: &PathBuf) -> Self {
+        let read = |key: &str, default: u64| {
+            database::get_setting(db_path, key)
+                .ok()
+                .flatten()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+
+        let mut checker = SettleChecker::new(
+            read("settle_seconds", DEFAULT_SETTLE_SECONDS),
+            read("settle_sample_ms", DEFAULT_SAMPLE_INTERVAL_MS),
+        );
+        checker.check_in_use = database::get_setting(db_path, "check_in_use")
+            .ok()
+            .flatten()
+            .map(|v| v != "false")
+            .unwrap_or(true);
+        checker
+    }
+
+    /// Check the files directly inside a directory
+    pub fn check_dir(&self, dir: &Path) -> HashMap<PathBuf, DeferReason> {
+        let paths: Vec<PathBuf> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.check_paths(&paths)
+    }
+
+    /// Only the in-use check, for files the user chose to move by hand
+    pub fn check_in_use(&self, paths: &[PathBuf]) -> HashMap<PathBuf, DeferReason> {
+        if !self.check_in_use || paths.is_empty() {
+            return HashMap::new();
+        }
+
+        let open_files = OpenFiles::scan();
+        paths
+            .iter()
+            .filter_map(|path| in_use::check_file(path, &open_files).map(|r| (path.clone(), r)))
+            .collect()
+    }
+
+    /// The in-use check for every file inside the given folders, before they are moved
+    pub fn check_trees_in_use(&self, dirs: &[PathBuf]) -> HashMap<PathBuf, DeferReason> {
+        if !self.check_in_use {
+            return HashMap::new();
+        }
+
+        let files: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|dir| walkdir::WalkDir::new(dir).follow_links(false))
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        self.check_in_use(&files)
+    }
+
+    /// Return the files that should be deferred, with the reason.
+    /// Files open in other apps are found with one scan for the batch, and
+    /// recently active files are sampled twice, sleeping once for the whole batch.
+    pub fn check_paths(&self, paths: &[PathBuf]) -> HashMap<PathBuf, DeferReason> {
+        let mut deferred = HashMap::new();
+        let mut samples: Vec<(PathBuf, u64)> = Vec::new();
+        let now = SystemTime::now();
+        let open_files = if self.check_in_use && !paths.is_empty() {
+            OpenFiles::scan()
+        } else {
+            OpenFiles::empty()
+        };
+
+        for path in paths {
+            if is_partial_download(path) {
+                deferred.insert(path.clone(), DeferReason::PartialDownload);
+                continue;
+            }
+
+            // Files another app has open or locked are never moved out from under it
+            if self.check_in_use {
+                if let Some(reason) = in_use::check_file(path, &open_files) {
+                    deferred.insert(path.clone(), reason);
+                    continue;
+                }
+            }
+
+            let metadata = match fs::metadata(path) {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+
+            // A modification time in the future counts as just modified
+            let age = metadata
+                .modified()
+                .ok()
+                .map(|t| now.duration_since(t).unwrap_or(Duration::ZERO));
+
+            match age {
+                Some(age) if age < self.settle_window => {
+                    deferred.insert(path.clone(), DeferReason::RecentlyModified);
+                }
+                Some(age) if age < ACTIVE_WINDOW => {
+                    samples.push((path.clone(), metadata.len()));
+                }
+                _ => {}
+            }
+        }
+
+        if samples.is_empty() || self.sample_interval.is_zero() {
+            return deferred;
+        }
+
+        thread::sleep(self.sample_interval);
+
+        for (path, size) in samples {
+            let changed = fs::metadata(&path).map(|m| m.len() != size).unwrap_or(false);
+            if changed {
+                deferred.insert(path, DeferReason::SizeChanging);
+            }
+        }
+
+        deferred
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_partial_download_extensions() {
+        assert!(is_partial_download(Path::new("/tmp/movie.mp4.crdownload")));
+        assert!(is_partial_download(Path::new("/tmp/setup.exe.PART")));
+        assert!(is_partial_download(Path::new("/tmp/file.download")));
+        assert!(!is_partial_download(Path::new("/tmp/report.pdf")));
+    }
+
+    #[test]
+    fn test_partial_sibling_placeholder() {
+        let dir = tempdir().unwrap();
+        let placeholder = dir.path().join("video.mp4");
+        fs::write(&placeholder, b"").unwrap();
+        fs::write(dir.path().join("video.mp4.part"), b"data").unwrap();
+
+        assert!(is_partial_download(&placeholder));
+    }
+
+    #[test]
+    fn test_recently_modified_is_deferred() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("new.txt");
+        fs::write(&path, b"hello").unwrap();
+
+        let checker = SettleChecker::new(60, 0);
+        let deferred = checker.check_dir(dir.path());
+        assert_eq!(deferred.get(&path), Some(&DeferReason::RecentlyModified));
+
+        let checker = SettleChecker::new(0, 0);
+        assert!(checker.check_dir(dir.path()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_in_use_ignores_recent_files() {
+        use fs2::FileExt;
+
+        let dir = tempdir().unwrap();
+        let fresh = dir.path().join("fresh.txt");
+        let locked = dir.path().join("locked.db");
+        fs::write(&fresh, b"hello").unwrap();
+        fs::write(&locked, b"data").unwrap();
+        let holder = fs::File::open(&locked).unwrap();
+        FileExt::lock_shared(&holder).unwrap();
+
+        let mut checker = SettleChecker::new(60, 0);
+        let paths = vec![fresh.clone(), locked.clone()];
+        let deferred = checker.check_in_use(&paths);
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred.get(&locked), Some(&DeferReason::Locked));
+
+        checker.check_in_use = false;
+        assert!(checker.check_in_use(&paths).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_trees_in_use_looks_inside_folders() {
+        use fs2::FileExt;
+
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("Reports").join("2024");
+        fs::create_dir_all(&nested).unwrap();
+        let locked = nested.join("budget.xlsx");
+        fs::write(&locked, b"data").unwrap();
+        fs::write(nested.join("notes.txt"), b"hello").unwrap();
+        let holder = fs::File::open(&locked).unwrap();
+        FileExt::lock_shared(&holder).unwrap();
+
+        let checker = SettleChecker::new(60, 0);
+        let deferred = checker.check_trees_in_use(&[dir.path().join("Reports")]);
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred.get(&locked), Some(&DeferReason::Locked));
+    }
+}
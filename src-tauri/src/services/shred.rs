@@ -0,0 +1,169 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Overwrite passes used when the caller does not choose
+pub const DEFAULT_PASSES: u32 = 3;
+
+/// Stored in the history entry of every shred, next to its irreversible marker
+pub const SHRED_WARNING: &str = concat!(
+    "파쇄는 되돌릴 수 없습니다. ",
+    "SSD나 복사-쓰기 파일 시스템(APFS, Btrfs, ZFS), 스냅샷과 백업에서는 ",
+    "덮어쓰기가 원래 데이터를 지운다고 보장할 수 없습니다."
+);
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Overwrite a file's contents in several passes (random data, the last pass zeros),
+/// rename it to a random name and unlink it. Folders are shredded file by file;
+/// links are removed without touching their targets.
+pub fn shred_path(path: &Path, passes: u32) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            shred_path(&entry?.path(), passes)?;
+        }
+        return fs::remove_dir(obscure_name(path)?);
+    }
+
+    if metadata.is_file() {
+        if metadata.permissions().readonly() {
+            make_writable(path, &metadata)?;
+        }
+        overwrite_file(path, metadata.len(), passes.max(1))?;
+    }
+
+    fs::remove_file(obscure_name(path)?)
+}
+
+#[cfg(unix)]
+fn make_writable(path: &Path, metadata: &fs::Metadata) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = metadata.permissions();
+    permissions.set_mode(permissions.mode() | 0o200);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn make_writable(path: &Path, metadata: &fs::Metadata) -> io::Result<()> {
+    let mut permissions = metadata.permissions();
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+/// Overwrite `len` bytes of the file in place, flushing each pass to disk
+fn overwrite_file(path: &Path, len: u64, passes: u32) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let mut rng = XorShift::seeded();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    for pass in 0..passes {
+        let last = pass + 1 == passes;
+        file.seek(SeekFrom::Start(0))?;
+
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(BUFFER_SIZE as u64) as usize;
+            if last {
+                buffer[..chunk].fill(0);
+            } else {
+                rng.fill(&mut buffer[..chunk]);
+            }
+            file.write_all(&buffer[..chunk])?;
+            remaining -= chunk as u64;
+        }
+
+        file.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Rename to a random name of the same folder so the original name is not left behind
+fn obscure_name(path: &Path) -> io::Result<PathBuf> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut rng = XorShift::seeded();
+
+    loop {
+        let candidate = parent.join(format!("{:016x}", rng.next()));
+        if fs::symlink_metadata(&candidate).is_err() {
+            fs::rename(path, &candidate)?;
+            return Ok(candidate);
+        }
+    }
+}
+
+/// Small non-cryptographic generator; the overwrite only has to replace the old bytes
+struct XorShift(u64);
+
+impl XorShift {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        XorShift((nanos ^ ((std::process::id() as u64) << 32)) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill(&mut self, buffer: &mut [u8]) {
+        for chunk in buffer.chunks_mut(8) {
+            let bytes = self.next().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_overwrite_replaces_contents() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("id-scan.jpg");
+        fs::write(&path, vec![0xAB; 100_000]).unwrap();
+
+        overwrite_file(&path, 100_000, 2).unwrap();
+        let contents = fs::read(&path).unwrap();
+        assert_eq!(contents.len(), 100_000);
+        assert!(contents.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_shred_folder_leaves_nothing() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("contracts");
+        fs::create_dir_all(folder.join("2024")).unwrap();
+        fs::write(folder.join("a.pdf"), b"secret").unwrap();
+        fs::write(folder.join("2024/b.pdf"), b"secret").unwrap();
+
+        shred_path(&folder, DEFAULT_PASSES).unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shred_link_keeps_target() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target.txt");
+        let link = dir.path().join("link.txt");
+        fs::write(&target, b"keep").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        shred_path(&link, 1).unwrap();
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert_eq!(fs::read(&target).unwrap(), b"keep");
+    }
+}
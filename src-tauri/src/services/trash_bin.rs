@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+/// Move a file or folder to the trash. Returns the deletion time (unix seconds) which,
+/// together with the original path, identifies the trash entry when undoing.
+pub fn move_to_trash(path: &Path) -> Result<i64, String> {
+    let deleted_at = chrono::Utc::now().timestamp();
+    trash::delete(path).map_err(|e| e.to_string())?;
+    Ok(deleted_at)
+}
+
+/// Restore trashed items given as (original path, deletion time).
+/// Returns one message per item that could not be restored.
+pub fn restore(entries: &[(PathBuf, Option<i64>)]) -> Vec<String> {
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    #[cfg(any(
+        target_os = "windows",
+        all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+    ))]
+    return listing::restore(entries);
+
+    // The trash cannot be listed here, so nothing can be restored automatically
+    #[cfg(not(any(
+        target_os = "windows",
+        all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+    )))]
+    vec![format!(
+        "이 운영체제에서는 휴지통 복원을 지원하지 않습니다. 휴지통에서 직접 복원해 주세요 ({}개 항목)",
+        entries.len()
+    )]
+}
+
+/// Parse recorded [path, deleted_at] tuples; a missing time is allowed for older records
+pub fn parse_entries(value: &serde_json::Value) -> Vec<(PathBuf, Option<i64>)> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry[0].as_str().filter(|p| !p.is_empty())?;
+            Some((PathBuf::from(path), entry[1].as_i64()))
+        })
+        .collect()
+}
+
+/// Trash listing and restore, available on Windows and freedesktop systems
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+mod listing {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// How long after the recorded time a trash entry may still be the one we deleted
+    /// (deleting a large folder can take a while)
+    const DELETION_WINDOW_SECS: i64 = 120;
+
+    /// Paths the trash may have recorded for `path`; it stores the canonical parent
+    fn identity_paths(path: &Path) -> Vec<PathBuf> {
+        let mut paths = vec![path.to_path_buf()];
+        if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+            if let Ok(parent) = fs::canonicalize(parent) {
+                paths.push(parent.join(name));
+            }
+        }
+        paths
+    }
+
+    /// The entry deleted from `path` at `deleted_at`: the earliest one deleted at or after
+    /// that time. Records without a time (older history) take the most recent entry.
+    pub(super) fn find_item<'a>(
+        items: &'a [trash::TrashItem],
+        path: &Path,
+        deleted_at: Option<i64>,
+    ) -> Option<&'a trash::TrashItem> {
+        let candidates = identity_paths(path);
+        let matching = items
+            .iter()
+            .filter(|item| candidates.contains(&item.original_path()));
+
+        match deleted_at {
+            // Trash times have second precision
+            Some(at) => matching
+                .filter(|item| {
+                    item.time_deleted >= at - 1 && item.time_deleted <= at + DELETION_WINDOW_SECS
+                })
+                .min_by_key(|item| item.time_deleted),
+            None => matching.max_by_key(|item| item.time_deleted),
+        }
+    }
+
+    pub(super) fn restore(entries: &[(PathBuf, Option<i64>)]) -> Vec<String> {
+        let items = match trash::os_limited::list() {
+            Ok(items) => items,
+            Err(e) => return vec![format!("휴지통 목록을 읽지 못했습니다: {}", e)],
+        };
+
+        let mut errors = Vec::new();
+        for (path, deleted_at) in entries {
+            if fs::symlink_metadata(path).is_ok() {
+                errors.push(format!(
+                    "원래 위치에 같은 이름의 파일이 있어 복원하지 않았습니다: {}",
+                    path.display()
+                ));
+                continue;
+            }
+
+            let Some(item) = find_item(&items, path, *deleted_at) else {
+                errors.push(format!(
+                    "휴지통에서 찾을 수 없습니다 (비워졌거나 이미 복원됨): {}",
+                    path.display()
+                ));
+                continue;
+            };
+
+            // One at a time, so a collision does not stop the remaining items
+            if let Err(e) = trash::os_limited::restore_all([item.clone()]) {
+                errors.push(format!("{}: {}", path.display(), e));
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::listing::find_item;
+    use super::*;
+    use std::ffi::OsString;
+
+    fn item(parent: &str, name: &str, time_deleted: i64) -> trash::TrashItem {
+        trash::TrashItem {
+            id: OsString::from(format!("{}-{}", name, time_deleted)),
+            name: name.into(),
+            original_parent: PathBuf::from(parent),
+            time_deleted,
+        }
+    }
+
+    #[test]
+    fn test_find_item_by_path_and_time() {
+        let items = vec![
+            item("/nonexistent/docs", "a.txt", 1_000),
+            item("/nonexistent/docs", "a.txt", 2_000),
+            item("/nonexistent/docs", "a.txt", 2_050),
+            item("/nonexistent/docs", "b.txt", 2_000),
+        ];
+        let path = Path::new("/nonexistent/docs/a.txt");
+
+        assert_eq!(find_item(&items, path, Some(2_000)).unwrap().time_deleted, 2_000);
+        assert_eq!(find_item(&items, path, Some(999)).unwrap().time_deleted, 1_000);
+        assert!(find_item(&items, path, Some(5_000)).is_none());
+        assert_eq!(find_item(&items, path, None).unwrap().time_deleted, 2_050);
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let value = serde_json::json!([["/a.txt", 100], ["/b.txt"], [""]]);
+        let entries = parse_entries(&value);
+
+        assert_eq!(
+            entries,
+            vec![(PathBuf::from("/a.txt"), Some(100)), (PathBuf::from("/b.txt"), None)]
+        );
+    }
+}
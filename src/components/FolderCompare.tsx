@@ -192,7 +192,9 @@ export default function FolderCompare() {
         if (result.success) {
           toast({
             title: "머지 완료",
-            description: `${result.filesCopied}개 복사, ${result.filesOverwritten}개 덮어씀, ${result.filesSkipped}개 건너뜀`,
+            description:
+              `${result.filesCopied}개 복사, ${result.filesOverwritten}개 덮어씀, ${result.filesSkipped}개 건너뜀` +
+              (result.filesDeferred > 0 ? `, ${result.filesDeferred}개 사용 중` : ""),
           });
           // Refresh comparison
           await handleCompare();
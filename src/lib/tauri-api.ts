@@ -4,12 +4,18 @@ import type {
   AppSettings,
   HistoryItem,
   OverwriteStrategy,
+  BatchResult,
   OrganizePreview,
   OrganizeOptions,
   OrganizeResult,
+  FlattenOptions,
+  FlattenMove,
+  FlattenResult,
   RenameRule,
   RenamePreview,
   RenameResult,
+  RenamePreset,
+  SettingsImportResult,
   FolderStats,
   DuplicateGroup,
   FolderTreeNode,
@@ -24,6 +30,15 @@ import type {
   UnifiedPreview,
   UnifiedOrganizeResult,
   ExtensionMapping,
+  PinnedFile,
+  QuarantinedFile,
+  QuarantineResult,
+  ExtractResult,
+  CreateArchiveResult,
+  Job,
+  JobSpec,
+  PinType,
+  OrganizeProfile,
 } from './types';
 
 // Check if running in Tauri environment
@@ -98,8 +113,34 @@ export const fileApi = {
     return invoke<string>('copy_file', { source, dest, overwrite });
   },
 
-  deleteFile: async (path: string, toTrash: boolean = true): Promise<void> => {
-    return invoke<void>('delete_file', { path, toTrash });
+  // shred (with toTrash false) overwrites the contents first; it cannot be undone and is
+  // not reliable on SSDs or copy-on-write file systems
+  deleteFile: async (
+    path: string,
+    toTrash: boolean = true,
+    shred: boolean = false
+  ): Promise<void> => {
+    return invoke<void>('delete_file', { path, toTrash, shred });
+  },
+
+  batchMove: async (
+    sources: string[],
+    destDir: string,
+    overwrite: OverwriteStrategy = 'rename'
+  ): Promise<BatchResult> => {
+    return invoke<BatchResult>('batch_move', { sources, destDir, overwrite });
+  },
+
+  batchCopy: async (
+    sources: string[],
+    destDir: string,
+    overwrite: OverwriteStrategy = 'rename'
+  ): Promise<BatchResult> => {
+    return invoke<BatchResult>('batch_copy', { sources, destDir, overwrite });
+  },
+
+  batchDelete: async (paths: string[], toTrash: boolean = true): Promise<BatchResult> => {
+    return invoke<BatchResult>('batch_delete', { paths, toTrash });
   },
 
   renameFile: async (path: string, newName: string): Promise<string> => {
@@ -126,6 +167,14 @@ export const settingsApi = {
         showHiddenFiles: false,
         confirmBeforeDelete: true,
         useTrash: true,
+        settleSeconds: 5,
+        checkInUse: true,
+        symlinkPolicy: 'link',
+        specialFilePolicy: 'skip',
+        useQuarantine: false,
+        quarantinePath: '',
+        quarantineRetentionDays: 30,
+        maxConcurrentJobs: 2,
       };
     }
     return invoke<AppSettings>('get_settings');
@@ -136,13 +185,25 @@ export const settingsApi = {
   ): Promise<AppSettings> => {
     return invoke<AppSettings>('update_settings', { settings });
   },
+
+  // Settings and rename presets as one JSON file
+  exportSettings: async (filePath: string): Promise<void> => {
+    return invoke<void>('export_settings', { filePath });
+  },
+
+  importSettings: async (filePath: string): Promise<SettingsImportResult> => {
+    return invoke<SettingsImportResult>('import_settings', { filePath });
+  },
 };
 
 // Organizer API
 export const organizerApi = {
-  previewOrganization: async (sourcePath: string): Promise<OrganizePreview[]> => {
+  previewOrganization: async (
+    sourcePath: string,
+    options?: OrganizeOptions
+  ): Promise<OrganizePreview[]> => {
     if (!isTauri()) return [];
-    return invoke<OrganizePreview[]>('preview_organization', { sourcePath });
+    return invoke<OrganizePreview[]>('preview_organization', { sourcePath, options });
   },
 
   executeOrganization: async (
@@ -151,6 +212,108 @@ export const organizerApi = {
   ): Promise<OrganizeResult> => {
     return invoke<OrganizeResult>('execute_organization', { sourcePath, options });
   },
+
+  previewFlatten: async (
+    sourcePath: string,
+    options: FlattenOptions
+  ): Promise<FlattenMove[]> => {
+    if (!isTauri()) return [];
+    return invoke<FlattenMove[]>('preview_flatten', { sourcePath, options });
+  },
+
+  flattenFolder: async (
+    sourcePath: string,
+    options: FlattenOptions
+  ): Promise<FlattenResult> => {
+    return invoke<FlattenResult>('flatten_folder', { sourcePath, options });
+  },
+};
+
+// Pin API (고정 파일)
+export const pinApi = {
+  getPins: async (): Promise<PinnedFile[]> => {
+    if (!isTauri()) return [];
+    return invoke<PinnedFile[]>('get_pins');
+  },
+
+  // For 'hash' pins, pattern is the path of the file to pin by content
+  addPin: async (pattern: string, pinType: PinType): Promise<PinnedFile> => {
+    return invoke<PinnedFile>('add_pin', { pattern, pinType });
+  },
+
+  removePin: async (id: number): Promise<void> => {
+    return invoke<void>('remove_pin', { id });
+  },
+};
+
+// Quarantine API (격리 보관함)
+export const quarantineApi = {
+  getQuarantinedFiles: async (): Promise<QuarantinedFile[]> => {
+    if (!isTauri()) return [];
+    return invoke<QuarantinedFile[]>('get_quarantined_files');
+  },
+
+  restore: async (ids: number[]): Promise<QuarantineResult> => {
+    return invoke<QuarantineResult>('restore_quarantined', { ids });
+  },
+
+  purge: async (ids: number[]): Promise<QuarantineResult> => {
+    return invoke<QuarantineResult>('purge_quarantined', { ids });
+  },
+
+  // Purge files past the retention period (also done at startup)
+  purgeExpired: async (): Promise<QuarantineResult> => {
+    return invoke<QuarantineResult>('purge_expired_quarantine');
+  },
+};
+
+// Job API (백그라운드 작업, progress via the 'job-update' event)
+export const jobApi = {
+  enqueue: async (spec: JobSpec): Promise<Job> => {
+    return invoke<Job>('enqueue_job', { kind: spec.kind, params: spec.params });
+  },
+
+  listJobs: async (): Promise<Job[]> => {
+    if (!isTauri()) return [];
+    return invoke<Job[]>('list_jobs');
+  },
+
+  pause: async (id: number): Promise<void> => {
+    return invoke<void>('pause_job', { id });
+  },
+
+  resume: async (id: number): Promise<void> => {
+    return invoke<void>('resume_job', { id });
+  },
+
+  cancel: async (id: number): Promise<void> => {
+    return invoke<void>('cancel_job', { id });
+  },
+};
+
+// Organize profile API
+export const profileApi = {
+  getProfiles: async (): Promise<OrganizeProfile[]> => {
+    if (!isTauri()) return [];
+    return invoke<OrganizeProfile[]>('get_profiles');
+  },
+
+  saveProfile: async (profile: OrganizeProfile): Promise<OrganizeProfile> => {
+    return invoke<OrganizeProfile>('save_profile', { profile });
+  },
+
+  deleteProfile: async (id: number): Promise<void> => {
+    return invoke<void>('delete_profile', { id });
+  },
+
+  previewProfile: async (id: number): Promise<UnifiedPreview[]> => {
+    if (!isTauri()) return [];
+    return invoke<UnifiedPreview[]>('preview_profile', { id });
+  },
+
+  executeProfile: async (id: number, excludedDestinations?: string[]): Promise<UnifiedOrganizeResult> => {
+    return invoke<UnifiedOrganizeResult>('execute_profile', { id, excludedDestinations });
+  },
 };
 
 // Renamer API
@@ -169,6 +332,28 @@ export const renamerApi = {
   ): Promise<RenameResult> => {
     return invoke<RenameResult>('execute_rename', { filePaths, rules });
   },
+
+  getPresets: async (): Promise<RenamePreset[]> => {
+    if (!isTauri()) return [];
+    return invoke<RenamePreset[]>('get_rename_presets');
+  },
+
+  savePreset: async (preset: RenamePreset): Promise<RenamePreset> => {
+    return invoke<RenamePreset>('save_rename_preset', { preset });
+  },
+
+  deletePreset: async (id: number): Promise<void> => {
+    return invoke<void>('delete_rename_preset', { id });
+  },
+
+  previewPreset: async (id: number, filePaths: string[]): Promise<RenamePreview[]> => {
+    if (!isTauri()) return [];
+    return invoke<RenamePreview[]>('preview_rename_preset', { id, filePaths });
+  },
+
+  executePreset: async (id: number, filePaths: string[]): Promise<RenameResult> => {
+    return invoke<RenameResult>('execute_rename_preset', { id, filePaths });
+  },
 };
 
 // History API
@@ -438,6 +623,27 @@ export const backupApi = {
   },
 };
 
+// Archive API (압축 해제 / 압축)
+export const archiveApi = {
+  extractArchive: async (
+    archivePath: string,
+    deleteArchive = false
+  ): Promise<ExtractResult> => {
+    if (!isTauri()) {
+      throw new Error('Archive extraction is only available in desktop app');
+    }
+    return invoke<ExtractResult>('extract_archive', { archivePath, deleteArchive });
+  },
+
+  // The format follows the extension of archivePath
+  createArchive: async (paths: string[], archivePath: string): Promise<CreateArchiveResult> => {
+    if (!isTauri()) {
+      throw new Error('Archive creation is only available in desktop app');
+    }
+    return invoke<CreateArchiveResult>('create_archive', { paths, archivePath });
+  },
+};
+
 // Folder Compare API
 export const folderCompareApi = {
   compareFolders: async (sourcePath: string, targetPath: string): Promise<CompareSummary> => {
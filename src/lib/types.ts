@@ -10,6 +10,8 @@ export interface FileInfo {
   isDirectory: boolean;
   isHidden: boolean;
   category: FileCategory;
+  isSymlink: boolean;
+  linkTarget?: string;
 }
 
 export type FileCategory =
@@ -22,6 +24,9 @@ export type FileCategory =
   | 'code'
   | 'others';
 
+// Date source for date subfolders and date renames
+export type DateSource = 'exif' | 'filename' | 'created' | 'modified';
+
 // Drive info types
 export interface DriveInfo {
   name: string;
@@ -46,12 +51,24 @@ export interface AppSettings {
   showHiddenFiles: boolean;
   confirmBeforeDelete: boolean;
   useTrash: boolean;
+  settleSeconds: number;
+  checkInUse: boolean; // defer files open or locked in another app
+  symlinkPolicy: SymlinkPolicy;
+  specialFilePolicy: SpecialFilePolicy;
+  useQuarantine: boolean;
+  quarantinePath: string; // empty: quarantine folder in the app data folder
+  quarantineRetentionDays: number; // 0: keep until purged
+  maxConcurrentJobs: number;
 }
 
+// skip: leave out, link: treat the link itself as a file, follow: resolve to the target
+export type SymlinkPolicy = 'skip' | 'link' | 'follow';
+export type SpecialFilePolicy = 'skip' | 'include';
+
 // History types
 export interface HistoryItem {
   id: number;
-  operationType: 'move' | 'copy' | 'delete' | 'rename' | 'organize';
+  operationType: 'move' | 'copy' | 'delete' | 'rename' | 'organize' | 'extract' | 'archive';
   description: string;
   details?: string;
   filesAffected: number;
@@ -59,9 +76,105 @@ export interface HistoryItem {
   createdAt: string;
 }
 
+// Pinned files (never moved by organize/rules)
+export type PinType = 'path' | 'glob' | 'hash';
+
+export interface PinnedFile {
+  id?: number;
+  pattern: string;
+  pinType: PinType;
+  fileSize?: number;
+  label?: string;
+  createdAt?: string;
+}
+
+// Quarantine (files removed by rules or duplicate cleanup)
+export interface QuarantinedFile {
+  id?: number;
+  originalPath: string;
+  storedPath: string;
+  reason: string;
+  size: number;
+  quarantinedAt?: string;
+}
+
+export interface QuarantineResult {
+  processed: number;
+  errors: string[];
+}
+
+// Archives (zip, tar, tar.gz, tar.zst)
+export interface ExtractResult {
+  success: boolean;
+  extractedPath: string; // folder named after the archive, next to it
+  filesExtracted: number;
+  totalSize: number;
+  skipped: string[]; // links, special files and paths outside the folder
+  archiveDeleted: boolean;
+  errors: string[];
+  historyId: number;
+}
+
+export interface CreateArchiveResult {
+  archivePath: string; // numbered when the requested name was taken
+  filesAdded: number;
+  totalSize: number;
+  skipped: string[];
+  historyId: number;
+}
+
+// Background jobs
+export type JobStatus = 'queued' | 'running' | 'paused' | 'completed' | 'failed' | 'cancelled';
+
+export type JobSpec =
+  | { kind: 'organize'; params: { sourcePath: string; options: OrganizeOptions } }
+  | { kind: 'backup'; params: { backupLocation?: string } }
+  | {
+      kind: 'merge';
+      params: { sourcePath: string; targetPath: string; options: MergeOptions };
+    }
+  | { kind: 'dedupe'; params: { path: string } };
+
+export interface Job {
+  id: number;
+  kind: JobSpec['kind'];
+  params: Record<string, unknown>;
+  status: JobStatus;
+  paths: string[]; // jobs with overlapping paths run one after another
+  result?: unknown; // the operation's result, also kept for cancelled jobs
+  error?: string;
+  createdAt?: string;
+  startedAt?: string;
+  finishedAt?: string;
+}
+
+export interface DedupeResult {
+  filesRemoved: number;
+  bytesFreed: number;
+  errors: string[];
+  historyId: number;
+}
+
 // File operation types
 export type OverwriteStrategy = 'overwrite' | 'rename' | 'skip';
 
+export interface BatchResult {
+  success: boolean;
+  processed: number;
+  skipped: number;
+  failed: number;
+  errors: string[];
+  deferred: DeferredFile[];
+  historyId: number;
+}
+
+export interface BatchProgress {
+  operation: 'move' | 'copy' | 'delete';
+  current: number;
+  total: number;
+  path: string;
+}
+
 // Rule types
 export interface Rule {
   id?: number;
@@ -71,18 +184,21 @@ export interface Rule {
   conditions: Condition[];
   conditionLogic: 'AND' | 'OR';
   actionType: 'move' | 'copy' | 'rename' | 'delete';
+  // May use template tokens, e.g. '~/Music/{artist}/{album}'
   actionDestination?: string;
   actionRenamePattern?: string;
   createDateSubfolder: boolean;
+  dateSource?: DateSource;
 }
 
 export interface Condition {
-  field: 'name' | 'extension' | 'size' | 'createdDate' | 'modifiedDate';
+  field: 'name' | 'extension' | 'size' | 'createdDate' | 'modifiedDate' | 'category';
   operator:
     | 'equals'
     | 'contains'
     | 'startsWith'
     | 'endsWith'
+    | 'in'
     | 'greaterThan'
     | 'lessThan'
     | 'matches';
@@ -99,6 +215,8 @@ export interface ExecuteRulesResult {
   success: boolean;
   executedCount: number;
   skippedCount: number;
+  deferredCount: number;
+  deferred: DeferredFile[];
   errors: string[];
 }
 
@@ -110,6 +228,7 @@ export interface DefaultRule {
   destination: string;
   createDateSubfolder: boolean;
   priority: number;
+  dateSource?: DateSource;
   extensions?: string[]; // Optional: loaded separately
 }
 
@@ -124,11 +243,30 @@ export interface ExtensionMapping {
 // Unified preview result (통합 미리보기 결과)
 export interface UnifiedPreview {
   file: FileInfo;
-  matchType: 'custom' | 'default';
+  matchType: 'custom' | 'default' | 'kept' | 'deferred';
   rule?: Rule;
   defaultRule?: DefaultRule;
   action: string;
   destination: string;
+  dateFolder?: string;
+  dateSource?: DateSource;
+  deferReason?: DeferReason;
+}
+
+// Organize profile (여러 소스 폴더 정리 프로필)
+export interface ProfileOptions {
+  includeCustomRules: boolean;
+  handleDuplicates: OverwriteStrategy;
+}
+
+export interface OrganizeProfile {
+  id?: number;
+  name: string;
+  sources: string[];
+  destinationRoot?: string;
+  defaultRules: DefaultRule[];
+  options: ProfileOptions;
+  createdAt?: string;
 }
 
 // Unified organization result
@@ -136,6 +274,8 @@ export interface UnifiedOrganizeResult {
   success: boolean;
   filesMoved: number;
   filesSkipped: number;
+  filesDeferred: number;
+  deferred: DeferredFile[];
   errors: string[];
   historyId: number;
 }
@@ -149,7 +289,11 @@ export interface RenameRule {
     | 'sequence'
     | 'date'
     | 'case'
-    | 'regex';
+    | 'regex'
+    | 'template'
+    | 'normalize'
+    | 'sanitize'
+    | 'extension';
   findText?: string;
   replaceText?: string;
   prefix?: string;
@@ -157,10 +301,38 @@ export interface RenameRule {
   startNumber?: number;
   digitCount?: number;
   dateFormat?: string;
-  dateSource?: 'created' | 'modified';
+  dateSource?: DateSource;
   caseType?: 'upper' | 'lower' | 'title';
   regexPattern?: string;
   regexReplace?: string;
+  // e.g. '{date:%Y-%m-%d}_{name:slug}_{n:03}{ext:lower}' or '{artist} - {track:02} {title}{ext}'
+  template?: string;
+  normalizeForm?: 'nfc' | 'nfd';
+  sortBy?: 'input' | 'name' | 'date' | 'size' | 'custom';
+  sortDescending?: boolean;
+  customOrder?: string[];
+  counterScope?: 'global' | 'folder' | 'extension';
+  step?: number;
+  offset?: number;
+  // e.g. '{name} ({n} of {total})'
+  sequenceFormat?: string;
+  extensionAction?: 'lower' | 'upper' | 'unify' | 'sniff' | 'set';
+  newExtension?: string;
+  // Only files matching these conditions are changed by this rule
+  conditions?: Condition[];
+  conditionLogic?: 'AND' | 'OR';
+}
+
+export interface RenamePreset {
+  id?: number;
+  name: string;
+  rules: RenameRule[];
+  createdAt?: string;
+}
+
+export interface SettingsImportResult {
+  settingsImported: number;
+  presetsImported: number;
 }
 
 export interface RenamePreview {
@@ -169,12 +341,14 @@ export interface RenamePreview {
   newName: string;
   hasConflict: boolean;
   conflictMessage?: string;
+  warning?: string;
 }
 
 export interface RenameResult {
   success: boolean;
   renamedCount: number;
   failedCount: number;
+  cyclesResolved: number;
   errors: string[];
 }
 
@@ -185,18 +359,66 @@ export interface OrganizePreview {
   files: FileInfo[];
   destinationFolder: string;
   fileCount: number;
+  fileDates: FileDatePreview[];
+  deferred: DeferredFile[];
+}
+
+export type DeferReason =
+  | 'partialDownload'
+  | 'recentlyModified'
+  | 'sizeChanging'
+  | 'inUse'
+  | 'locked';
+
+export interface DeferredFile {
+  path: string;
+  reason: DeferReason;
+}
+
+export interface FileDatePreview {
+  path: string;
+  dateFolder: string;
+  dateSource?: DateSource;
 }
 
 export interface OrganizeOptions {
   createDateSubfolders: boolean;
   dateFormat: string;
-  handleDuplicates: string; // "overwrite", "rename", "skip"
+  dateSource?: DateSource;
+  handleDuplicates: string; // "overwrite", "rename", "skip", "dedupe"
 }
 
 export interface OrganizeResult {
   success: boolean;
   filesMoved: number;
   filesSkipped: number;
+  filesDeduplicated: number;
+  filesDeferred: number;
+  deferred: DeferredFile[];
+  errors: string[];
+  historyId: number;
+}
+
+// Flatten (폴더 평탄화) types
+export interface FlattenOptions {
+  targetDepth: number; // 0 = the folder itself
+  handleDuplicates: OverwriteStrategy;
+  removeEmptyFolders: boolean;
+}
+
+export interface FlattenMove {
+  from: string;
+  to: string;
+  action: 'move' | 'rename' | 'overwrite' | 'skip' | 'kept';
+}
+
+export interface FlattenResult {
+  success: boolean;
+  filesMoved: number;
+  filesSkipped: number;
+  filesDeferred: number;
+  deferred: DeferredFile[];
+  foldersRemoved: number;
   errors: string[];
   historyId: number;
 }
@@ -272,6 +494,8 @@ export interface MergeResult {
   filesCopied: number;
   filesSkipped: number;
   filesOverwritten: number;
+  filesDeferred: number;
+  deferred: DeferredFile[];
   bytesTransferred: number;
   errors: string[];
 }